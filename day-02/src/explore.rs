@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use crate::guide::{GuideReader, ParseMode};
+use crate::rules::Ruleset;
+use crate::{Outcome, Shape};
+
+struct Interpretation {
+    description: String,
+    score: usize,
+    wins: usize,
+    draws: usize,
+    losses: usize,
+}
+
+impl Interpretation {
+    fn new(description: String) -> Self {
+        return Interpretation {
+            description,
+            score: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        };
+    }
+
+    fn add_round(&mut self, rules: &Ruleset, player_shape: Shape, outcome: Outcome) {
+        self.score += rules.shape_score(player_shape) + outcome as usize;
+        match outcome {
+            Outcome::Lose => self.losses += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Win => self.wins += 1,
+        }
+    }
+}
+
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for perm in permutations(n - 1) {
+        for i in 0..n {
+            let mut p = perm.clone();
+            p.insert(i, n - 1);
+            result.push(p);
+        }
+    }
+    return result;
+}
+
+pub fn explore(
+    path: &Path,
+    rules: &Ruleset,
+    mode: ParseMode,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut guide = GuideReader::open(path, mode)?;
+    let mut rounds = Vec::new();
+
+    while let Some((line_num, round)) = guide.next_round(|a, b| {
+        let opponent_shape = a.parse_with(|s| rules.parse_opponent_shape(s))?;
+        let (player_shape, outcome) = b.parse_with(|s| rules.parse_second_column(s))?;
+        return Ok((opponent_shape, player_shape, outcome, b.text.to_string()));
+    })? {
+        rounds.push((line_num, round));
+    }
+    guide.report_skipped("Explore");
+
+    // Each reading only applies if every round's second column can be read that way
+    let shape_error = rounds
+        .iter()
+        .find(|(_, (_, shape, _, _))| shape.is_none())
+        .map(|(line_num, (_, _, _, key))| {
+            format!("line {line_num}: \"{key}\" is not a player letter")
+        });
+    let outcome_error = rounds
+        .iter()
+        .find(|(_, (_, _, outcome, _))| outcome.is_none())
+        .map(|(line_num, (_, _, _, key))| {
+            format!("line {line_num}: \"{key}\" is not an outcome letter")
+        });
+
+    let n = rules.num_shapes();
+    let mut interpretations = Vec::new();
+
+    if shape_error.is_none() {
+        for perm in permutations(n) {
+            let mapping: Vec<String> = (0..n)
+                .map(|i| {
+                    let key = rules.player_key(Shape(i));
+                    return format!("{key}={}", rules.shape_name(Shape(perm[i])));
+                })
+                .collect();
+            let mut interpretation = Interpretation::new(format!("Shape {}", mapping.join(" ")));
+
+            for (_, (opponent_shape, shape, _, _)) in rounds.iter() {
+                if let Some(shape) = shape {
+                    let player_shape = Shape(perm[shape.0]);
+                    let outcome = rules.outcome(player_shape, *opponent_shape);
+                    interpretation.add_round(rules, player_shape, outcome);
+                }
+            }
+            interpretations.push(interpretation);
+        }
+    }
+
+    // Guides for games with more shapes than outcomes can't always be read as outcomes
+    if outcome_error.is_none() {
+        let mapping: Vec<String> = [Outcome::Lose, Outcome::Draw, Outcome::Win]
+            .iter()
+            .map(|o| format!("{}={o:?}", rules.outcome_key(*o)))
+            .collect();
+        let mut interpretation = Interpretation::new(format!("Outcome {}", mapping.join(" ")));
+
+        for (_, (opponent_shape, _, outcome, _)) in rounds.iter() {
+            if let Some(outcome) = outcome {
+                let player_shape = rules.shape_for_outcome(*opponent_shape, *outcome);
+                interpretation.add_round(rules, player_shape, *outcome);
+            }
+        }
+        interpretations.push(interpretation);
+    }
+
+    // Stable sort so tied interpretations keep their generation order
+    interpretations.sort_by_key(|i| std::cmp::Reverse(i.score));
+
+    let width = interpretations
+        .iter()
+        .map(|i| i.description.len())
+        .max()
+        .unwrap_or_default();
+    println!(
+        "{:>4}  {:<width$}  {:>8}  {:>6}  {:>6}  {:>6}",
+        "Rank", "Interpretation", "Score", "Wins", "Draws", "Losses"
+    );
+
+    for (rank, i) in interpretations.iter().enumerate() {
+        println!(
+            "{:>4}  {:<width$}  {:>8}  {:>6}  {:>6}  {:>6}",
+            rank + 1,
+            i.description,
+            i.score,
+            i.wins,
+            i.draws,
+            i.losses
+        );
+    }
+
+    if let Some(e) = shape_error {
+        println!("Shape interpretations not applicable: {e}");
+    }
+    if let Some(e) = outcome_error {
+        println!("Outcome interpretation not applicable: {e}");
+    }
+    return Ok(());
+}
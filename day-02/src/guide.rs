@@ -0,0 +1,175 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    // Stop at the first malformed line
+    Strict,
+    // Skip malformed lines and report them once the guide has been read
+    Lenient,
+}
+
+pub struct GuideError {
+    line_num: usize,
+    column: usize,
+    message: String,
+}
+
+impl fmt::Display for GuideError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "Line {}, column {}: {}",
+            self.line_num, self.column, self.message
+        );
+    }
+}
+
+// Errors returned from main are printed with Debug, so keep the location readable there too
+impl fmt::Debug for GuideError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return fmt::Display::fmt(self, f);
+    }
+}
+
+impl std::error::Error for GuideError {}
+
+#[derive(Clone, Copy)]
+pub struct Token<'a> {
+    pub text: &'a str,
+    line_num: usize,
+    column: usize,
+}
+
+impl<'a> Token<'a> {
+    pub fn parse_with<T>(
+        &self,
+        parse: impl Fn(&str) -> Result<T, std::io::Error>,
+    ) -> Result<T, GuideError> {
+        return parse(self.text).map_err(|e| {
+            let uppercase = self.text.to_uppercase();
+            let message = if uppercase != self.text && parse(&uppercase).is_ok() {
+                format!(
+                    "lowercase \"{}\" is not accepted, use \"{uppercase}\"",
+                    self.text
+                )
+            } else {
+                format!("\"{}\" is not valid: {e}", self.text)
+            };
+
+            return GuideError {
+                line_num: self.line_num,
+                column: self.column,
+                message,
+            };
+        });
+    }
+}
+
+pub struct GuideReader {
+    reader: BufReader<File>,
+    line: String,
+    line_num: usize,
+    mode: ParseMode,
+    skipped: Vec<GuideError>,
+}
+
+impl GuideReader {
+    pub fn open(path: &Path, mode: ParseMode) -> Result<Self, Box<dyn std::error::Error>> {
+        let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+        return Ok(GuideReader {
+            reader: BufReader::new(file),
+            line: String::with_capacity(4),
+            line_num: 0,
+            mode,
+            skipped: Vec::new(),
+        });
+    }
+
+    // Reads rounds until one parses successfully, returning it along with its line number
+    pub fn next_round<T>(
+        &mut self,
+        parse: impl Fn(Token, Token) -> Result<T, GuideError>,
+    ) -> Result<Option<(usize, T)>, Box<dyn std::error::Error>> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            self.line_num += 1;
+
+            let result = match self.tokenize() {
+                Ok((first, second)) => parse(first, second),
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(round) => return Ok(Some((self.line_num, round))),
+                Err(e) if self.mode == ParseMode::Lenient => self.skipped.push(e),
+                Err(e) => return Err(e)?,
+            }
+        }
+    }
+
+    fn tokenize(&self) -> Result<(Token<'_>, Token<'_>), GuideError> {
+        let line = self.line.trim_end_matches(['\n', '\r']);
+        let mut tokens = Vec::with_capacity(2);
+        let mut start = None;
+
+        for (column, (i, c)) in line.char_indices().enumerate() {
+            if c.is_whitespace() {
+                if let Some((s, s_column)) = start.take() {
+                    tokens.push((&line[s..i], s_column));
+                }
+            } else if start.is_none() {
+                start = Some((i, column + 1));
+            }
+        }
+        if let Some((s, s_column)) = start {
+            tokens.push((&line[s..], s_column));
+        }
+
+        let error = |column: usize, message: String| GuideError {
+            line_num: self.line_num,
+            column,
+            message,
+        };
+
+        return match tokens.as_slice() {
+            [] => Err(error(1, String::from("blank line"))),
+            [_] => Err(error(
+                line.chars().count() + 1,
+                String::from("expected a second column"),
+            )),
+            [(first, first_column), (second, second_column)] => Ok((
+                Token {
+                    text: first,
+                    line_num: self.line_num,
+                    column: *first_column,
+                },
+                Token {
+                    text: second,
+                    line_num: self.line_num,
+                    column: *second_column,
+                },
+            )),
+            [_, _, (extra, extra_column), ..] => Err(error(
+                *extra_column,
+                format!("unexpected extra token \"{extra}\""),
+            )),
+        };
+    }
+
+    pub fn report_skipped(&self, label: &str) {
+        if self.skipped.is_empty() {
+            return;
+        }
+
+        eprintln!("{label} - Skipped {} malformed rounds:", self.skipped.len());
+        for e in self.skipped.iter() {
+            eprintln!("  {e}");
+        }
+    }
+}
@@ -1,128 +1,187 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+mod explore;
+mod guide;
+mod rules;
+mod simulate;
+mod tournament;
+mod trace;
+
+use guide::{GuideReader, ParseMode};
+use rules::Ruleset;
+use simulate::{AlwaysShape, FrequencyCounter, MarkovPredictor, RandomShape, Strategy};
+use std::path::Path;
 use std::str::FromStr;
+use tournament::{Reading, TieBreaker, TournamentOptions};
+use trace::{Trace, TraceFormat};
 
-#[derive(Clone, Copy)]
-enum Shape {
-    Rock = 1,
-    Paper = 2,
-    Scissors = 3,
-}
-
-impl Shape {
-    fn from_shape_and_outcome(shape: Shape, target_outcome: Outcome) -> Shape {
-        match (shape, target_outcome) {
-            (Shape::Rock, Outcome::Lose) => Shape::Scissors,
-            (Shape::Rock, Outcome::Draw) => Shape::Rock,
-            (Shape::Rock, Outcome::Win) => Shape::Paper,
-            (Shape::Paper, Outcome::Lose) => Shape::Rock,
-            (Shape::Paper, Outcome::Draw) => Shape::Paper,
-            (Shape::Paper, Outcome::Win) => Shape::Scissors,
-            (Shape::Scissors, Outcome::Lose) => Shape::Paper,
-            (Shape::Scissors, Outcome::Draw) => Shape::Scissors,
-            (Shape::Scissors, Outcome::Win) => Shape::Rock,
-        }
-    }
-}
-
-impl FromStr for Shape {
-    type Err = std::io::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "A" | "X" => Ok(Shape::Rock),
-            "B" | "Y" => Ok(Shape::Paper),
-            "C" | "Z" => Ok(Shape::Scissors),
-            _ => Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Shape character must only be A, B, C, X, Y, or Z",
-            )),
-        }
-    }
-}
+// Index of a shape within the cycle of its ruleset
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct Shape(usize);
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum Outcome {
     Lose = 0,
     Draw = 3,
     Win = 6,
 }
 
-impl Outcome {
-    fn from_shapes(player_shape: Shape, opponent_shape: Shape) -> Outcome {
-        match (opponent_shape, player_shape) {
-            (Shape::Rock, Shape::Rock) => Outcome::Draw,
-            (Shape::Rock, Shape::Paper) => Outcome::Win,
-            (Shape::Rock, Shape::Scissors) => Outcome::Lose,
-            (Shape::Paper, Shape::Rock) => Outcome::Lose,
-            (Shape::Paper, Shape::Paper) => Outcome::Draw,
-            (Shape::Paper, Shape::Scissors) => Outcome::Win,
-            (Shape::Scissors, Shape::Rock) => Outcome::Win,
-            (Shape::Scissors, Shape::Paper) => Outcome::Lose,
-            (Shape::Scissors, Shape::Scissors) => Outcome::Draw,
-        }
-    }
-}
-
-impl FromStr for Outcome {
-    type Err = std::io::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "X" => Ok(Outcome::Lose),
-            "Y" => Ok(Outcome::Draw),
-            "Z" => Ok(Outcome::Win),
-            _ => Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "Outcome character must only by X, Y, or Z",
-            )),
-        }
-    }
-}
-
-fn solution_part_1() -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
-    let mut reader = BufReader::new(file);
-    let mut line = String::with_capacity(3);
+fn solution_part_1(
+    path: &Path,
+    rules: &Ruleset,
+    mode: ParseMode,
+    mut trace: Option<&mut Trace>,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut guide = GuideReader::open(path, mode)?;
     let mut score = 0;
 
-    while reader.read_line(&mut line)? != 0 {
-        let mut shapes = line.split_whitespace();
-        let opponent_shape = Shape::from_str(shapes.next().unwrap_or_default())?;
-        let player_shape = Shape::from_str(shapes.next().unwrap_or_default())?;
-        let round_outcome = Outcome::from_shapes(player_shape, opponent_shape);
+    while let Some((line_num, (opponent_shape, player_shape))) = guide.next_round(|a, b| {
+        let opponent_shape = a.parse_with(|s| rules.parse_opponent_shape(s))?;
+        let player_shape = b.parse_with(|s| rules.parse_player_shape(s))?;
+        return Ok((opponent_shape, player_shape));
+    })? {
+        let round_outcome = rules.outcome(player_shape, opponent_shape);
 
-        score += player_shape as usize;
+        score += rules.shape_score(player_shape);
         score += round_outcome as usize;
-        line.clear();
+
+        if let Some(trace) = trace.as_mut() {
+            trace.record(rules, line_num, opponent_shape, player_shape, round_outcome);
+        }
     }
 
+    guide.report_skipped("Part 1");
     return Ok(score);
 }
 
-fn solution_part_2() -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
-    let mut reader = BufReader::new(file);
-    let mut line = String::with_capacity(3);
+fn solution_part_2(
+    path: &Path,
+    rules: &Ruleset,
+    mode: ParseMode,
+    mut trace: Option<&mut Trace>,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut guide = GuideReader::open(path, mode)?;
     let mut score = 0;
 
-    while reader.read_line(&mut line)? != 0 {
-        let mut shapes = line.split_whitespace();
-        let opponent_shape = Shape::from_str(shapes.next().unwrap_or_default())?;
-        let target_outcome = Outcome::from_str(shapes.next().unwrap_or_default())?;
-        let target_player_shape = Shape::from_shape_and_outcome(opponent_shape, target_outcome);
+    while let Some((line_num, (opponent_shape, target_outcome))) = guide.next_round(|a, b| {
+        let opponent_shape = a.parse_with(|s| rules.parse_opponent_shape(s))?;
+        let target_outcome = b.parse_with(|s| rules.parse_outcome(s))?;
+        return Ok((opponent_shape, target_outcome));
+    })? {
+        let target_player_shape = rules.shape_for_outcome(opponent_shape, target_outcome);
 
         score += target_outcome as usize;
-        score += target_player_shape as usize;
-        line.clear();
+        score += rules.shape_score(target_player_shape);
+
+        if let Some(trace) = trace.as_mut() {
+            trace.record(
+                rules,
+                line_num,
+                opponent_shape,
+                target_player_shape,
+                target_outcome,
+            );
+        }
     }
 
+    guide.report_skipped("Part 2");
     return Ok(score);
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let part_1 = solution_part_1()?;
-    let part_2 = solution_part_2()?;
+    let mut args = std::env::args().skip(1);
+    let mut rules = Ruleset::classic();
+    let mut inputs = Vec::new();
+    let mut explore = false;
+    let mut tournament = None;
+    let mut simulate = false;
+    let mut trace_format = None;
+    let mut mode = ParseMode::Strict;
+    let mut seed = 0;
+    let mut markov_order = 2;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rules" => {
+                let name = args
+                    .next()
+                    .ok_or("--rules requires a preset name or file path")?;
+                rules = match Ruleset::from_preset(&name) {
+                    Some(preset) => preset,
+                    None => Ruleset::from_file(Path::new(&name))?,
+                };
+            }
+            "--strict" => mode = ParseMode::Strict,
+            "--lenient" => mode = ParseMode::Lenient,
+            "--explore" => explore = true,
+            "--simulate" => simulate = true,
+            "--trace" => {
+                let format = args.next().ok_or("--trace requires csv or json")?;
+                trace_format = Some(TraceFormat::from_str(&format)?);
+            }
+            "--seed" => seed = args.next().ok_or("--seed requires a value")?.parse()?,
+            "--markov-order" => {
+                markov_order = args
+                    .next()
+                    .ok_or("--markov-order requires a value")?
+                    .parse()?;
+            }
+            "--tournament" => tournament = Some(TournamentOptions::default()),
+            "--rounds" | "--reading" | "--tie-breakers" => {
+                let options = tournament
+                    .as_mut()
+                    .ok_or(format!("{arg} is only valid after --tournament"))?;
+                let value = args.next().ok_or(format!("{arg} requires a value"))?;
+
+                match arg.as_str() {
+                    "--rounds" => options.rounds = Some(value.parse::<usize>()?),
+                    "--reading" => options.reading = Reading::from_str(&value)?,
+                    _ => {
+                        options.tie_breakers = value
+                            .split(',')
+                            .map(TieBreaker::from_str)
+                            .collect::<Result<_, _>>()?;
+                    }
+                }
+            }
+            _ => inputs.push(arg),
+        }
+    }
+
+    if let Some(options) = tournament {
+        return tournament::run_tournament(&inputs, &rules, mode, &options);
+    }
+
+    let input = match inputs.as_slice() {
+        [] => "input.txt",
+        [input] => input.as_str(),
+        _ => return Err("Only tournaments accept more than one input file")?,
+    };
+
+    if explore {
+        return explore::explore(Path::new(input), &rules, mode);
+    }
+
+    if let Some(format) = trace_format {
+        let mut trace = Trace::new();
+        trace.start_part(1);
+        solution_part_1(Path::new(input), &rules, mode, Some(&mut trace))?;
+        trace.start_part(2);
+        solution_part_2(Path::new(input), &rules, mode, Some(&mut trace))?;
+        return Ok(trace.write(format, &mut std::io::stdout().lock())?);
+    }
+
+    let part_1 = solution_part_1(Path::new(input), &rules, mode, None)?;
+
+    if simulate {
+        let mut strategies: Vec<Box<dyn Strategy>> = vec![
+            Box::new(AlwaysShape::new(Shape(0))),
+            Box::new(RandomShape::new(seed)),
+            Box::new(FrequencyCounter::new()),
+            Box::new(MarkovPredictor::new(markov_order)),
+        ];
+        return simulate::simulate(Path::new(input), &rules, mode, &mut strategies, part_1);
+    }
+
+    let part_2 = solution_part_2(Path::new(input), &rules, mode, None)?;
 
     println!("Part 1 - Total score: {part_1}");
     println!("Part 2 - Total score: {part_2}");
@@ -0,0 +1,232 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::{Outcome, Shape};
+
+// Each line lists a shape's name, score, opponent letter and player letter. Shapes are listed in
+// cycle order, with every shape beating the (n - 1) / 2 shapes listed before it (wrapping around)
+const CLASSIC_RULES: &str = "\
+Rock 1 A X
+Paper 2 B Y
+Scissors 3 C Z
+outcomes X Y Z
+";
+
+const SPOCK_RULES: &str = "\
+Rock 1 A V
+Spock 5 B W
+Paper 2 C X
+Lizard 4 D Y
+Scissors 3 E Z
+outcomes X Y Z
+";
+
+struct ShapeRule {
+    name: String,
+    score: usize,
+    opponent_key: String,
+    player_key: String,
+}
+
+pub struct Ruleset {
+    shapes: Vec<ShapeRule>,
+    outcome_keys: [String; 3],
+}
+
+impl Ruleset {
+    pub fn classic() -> Self {
+        return Ruleset::from_str(CLASSIC_RULES).expect("Built-in ruleset should be valid");
+    }
+
+    pub fn from_preset(name: &str) -> Option<Self> {
+        let rules = match name {
+            "classic" => CLASSIC_RULES,
+            "spock" => SPOCK_RULES,
+            _ => return None,
+        };
+        return Some(Ruleset::from_str(rules).expect("Built-in ruleset should be valid"));
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let rules =
+            fs::read_to_string(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+        return Ok(Ruleset::from_str(&rules)?);
+    }
+
+    pub fn num_shapes(&self) -> usize {
+        return self.shapes.len();
+    }
+
+    pub fn shape_name(&self, shape: Shape) -> &str {
+        return &self.shapes[shape.0].name;
+    }
+
+    pub fn player_key(&self, shape: Shape) -> &str {
+        return &self.shapes[shape.0].player_key;
+    }
+
+    pub fn outcome_key(&self, outcome: Outcome) -> &str {
+        return match outcome {
+            Outcome::Lose => &self.outcome_keys[0],
+            Outcome::Draw => &self.outcome_keys[1],
+            Outcome::Win => &self.outcome_keys[2],
+        };
+    }
+
+    pub fn shape_score(&self, shape: Shape) -> usize {
+        return self.shapes[shape.0].score;
+    }
+
+    pub fn outcome(&self, player_shape: Shape, opponent_shape: Shape) -> Outcome {
+        let n = self.num_shapes();
+        let distance = (player_shape.0 + n - opponent_shape.0) % n;
+
+        if distance == 0 {
+            return Outcome::Draw;
+        } else if distance <= n / 2 {
+            return Outcome::Win;
+        } else {
+            return Outcome::Lose;
+        }
+    }
+
+    // With more than three shapes there are several shapes that reach the target outcome, so the
+    // nearest one in the cycle is chosen
+    pub fn shape_for_outcome(&self, opponent_shape: Shape, target_outcome: Outcome) -> Shape {
+        let n = self.num_shapes();
+        return match target_outcome {
+            Outcome::Lose => Shape((opponent_shape.0 + n - 1) % n),
+            Outcome::Draw => opponent_shape,
+            Outcome::Win => Shape((opponent_shape.0 + 1) % n),
+        };
+    }
+
+    pub fn parse_opponent_shape(&self, s: &str) -> Result<Shape, std::io::Error> {
+        return match self.shapes.iter().position(|r| r.opponent_key == s) {
+            Some(i) => Ok(Shape(i)),
+            None => Err(self.key_error("Opponent shape", |r| &r.opponent_key)),
+        };
+    }
+
+    pub fn parse_player_shape(&self, s: &str) -> Result<Shape, std::io::Error> {
+        return match self.shapes.iter().position(|r| r.player_key == s) {
+            Some(i) => Ok(Shape(i)),
+            None => Err(self.key_error("Player shape", |r| &r.player_key)),
+        };
+    }
+
+    pub fn parse_outcome(&self, s: &str) -> Result<Outcome, std::io::Error> {
+        return match self.outcome_keys.iter().position(|k| k == s) {
+            Some(0) => Ok(Outcome::Lose),
+            Some(1) => Ok(Outcome::Draw),
+            Some(2) => Ok(Outcome::Win),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Outcome character must only be {}",
+                    self.outcome_keys.join(", ")
+                ),
+            )),
+        };
+    }
+
+    // The second column of a guide is a player shape or an outcome depending on how it is read,
+    // so it is valid as long as one of the readings accepts it
+    pub fn parse_second_column(
+        &self,
+        s: &str,
+    ) -> Result<(Option<Shape>, Option<Outcome>), std::io::Error> {
+        let shape = self.parse_player_shape(s).ok();
+        let outcome = self.parse_outcome(s).ok();
+
+        if shape.is_none() && outcome.is_none() {
+            let keys: Vec<&str> = self.shapes.iter().map(|r| r.player_key.as_str()).collect();
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Second column must be a player letter ({}) or an outcome letter ({})",
+                    keys.join(", "),
+                    self.outcome_keys.join(", ")
+                ),
+            ));
+        }
+        return Ok((shape, outcome));
+    }
+
+    fn key_error(&self, kind: &str, key: impl Fn(&ShapeRule) -> &String) -> std::io::Error {
+        let keys: Vec<&str> = self.shapes.iter().map(|r| key(r).as_str()).collect();
+        return std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("{kind} character must only be {}", keys.join(", ")),
+        );
+    }
+}
+
+impl FromStr for Ruleset {
+    type Err = std::io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |msg: String| std::io::Error::new(std::io::ErrorKind::InvalidData, msg);
+        let mut shapes: Vec<ShapeRule> = Vec::new();
+        let mut outcome_keys = None;
+
+        for (i, line) in s.lines().enumerate() {
+            let line_num = i + 1;
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+
+            match tokens.as_slice() {
+                [] => {}
+                [comment, ..] if comment.starts_with('#') => {}
+                ["outcomes", lose, draw, win] => {
+                    outcome_keys = Some([lose.to_string(), draw.to_string(), win.to_string()]);
+                }
+                [name, score, opponent_key, player_key] => {
+                    let score = score
+                        .parse::<usize>()
+                        .map_err(|e| invalid(format!("Line {line_num}: invalid score: {e}")))?;
+
+                    if shapes.iter().any(|r| r.name == *name) {
+                        return Err(invalid(format!("Line {line_num}: duplicate shape {name}")));
+                    }
+                    if shapes.iter().any(|r| r.opponent_key == *opponent_key) {
+                        return Err(invalid(format!(
+                            "Line {line_num}: duplicate opponent letter {opponent_key}"
+                        )));
+                    }
+                    if shapes.iter().any(|r| r.player_key == *player_key) {
+                        return Err(invalid(format!(
+                            "Line {line_num}: duplicate player letter {player_key}"
+                        )));
+                    }
+
+                    shapes.push(ShapeRule {
+                        name: name.to_string(),
+                        score,
+                        opponent_key: opponent_key.to_string(),
+                        player_key: player_key.to_string(),
+                    });
+                }
+                _ => {
+                    return Err(invalid(format!(
+                        "Line {line_num}: expected \"<name> <score> <opponent letter> <player letter>\" or \"outcomes <lose> <draw> <win>\""
+                    )));
+                }
+            }
+        }
+
+        if shapes.len() < 3 || shapes.len().is_multiple_of(2) {
+            return Err(invalid(format!(
+                "A cyclic game needs an odd number of shapes of at least 3, found {}",
+                shapes.len()
+            )));
+        }
+
+        let outcome_keys = outcome_keys
+            .ok_or_else(|| invalid("Ruleset is missing an outcomes line".to_string()))?;
+        return Ok(Ruleset {
+            shapes,
+            outcome_keys,
+        });
+    }
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::guide::{GuideReader, ParseMode};
+use crate::rules::Ruleset;
+use crate::{Outcome, Shape};
+
+// Strategies pick a shape each round before seeing the opponent's shape, and are then shown it
+pub trait Strategy {
+    fn name(&self, rules: &Ruleset) -> String;
+    fn choose_shape(&mut self, rules: &Ruleset) -> Shape;
+    fn observe(&mut self, opponent_shape: Shape);
+}
+
+pub struct AlwaysShape {
+    shape: Shape,
+}
+
+impl AlwaysShape {
+    pub fn new(shape: Shape) -> Self {
+        return AlwaysShape { shape };
+    }
+}
+
+impl Strategy for AlwaysShape {
+    fn name(&self, rules: &Ruleset) -> String {
+        return format!("Always {}", rules.shape_name(self.shape));
+    }
+
+    fn choose_shape(&mut self, _rules: &Ruleset) -> Shape {
+        return self.shape;
+    }
+
+    fn observe(&mut self, _opponent_shape: Shape) {}
+}
+
+// SplitMix64, which gives a well mixed sequence for any seed including zero
+pub struct RandomShape {
+    seed: u64,
+    state: u64,
+}
+
+impl RandomShape {
+    pub fn new(seed: u64) -> Self {
+        return RandomShape { seed, state: seed };
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        return z ^ (z >> 31);
+    }
+}
+
+impl Strategy for RandomShape {
+    fn name(&self, _rules: &Ruleset) -> String {
+        return format!("Random (seed {})", self.seed);
+    }
+
+    fn choose_shape(&mut self, rules: &Ruleset) -> Shape {
+        return Shape((self.next_u64() % rules.num_shapes() as u64) as usize);
+    }
+
+    fn observe(&mut self, _opponent_shape: Shape) {}
+}
+
+fn most_common(counts: &[usize]) -> Option<Shape> {
+    let mut best: Option<(usize, usize)> = None;
+    for (i, count) in counts.iter().enumerate() {
+        if *count > 0 && best.is_none_or(|(_, c)| *count > c) {
+            best = Some((i, *count));
+        }
+    }
+    return best.map(|(i, _)| Shape(i));
+}
+
+fn counter_shape(rules: &Ruleset, predicted: Option<Shape>) -> Shape {
+    return match predicted {
+        Some(shape) => rules.shape_for_outcome(shape, Outcome::Win),
+        None => Shape(0),
+    };
+}
+
+pub struct FrequencyCounter {
+    counts: Vec<usize>,
+}
+
+impl FrequencyCounter {
+    pub fn new() -> Self {
+        return FrequencyCounter { counts: Vec::new() };
+    }
+}
+
+impl Strategy for FrequencyCounter {
+    fn name(&self, _rules: &Ruleset) -> String {
+        return String::from("Frequency counter");
+    }
+
+    fn choose_shape(&mut self, rules: &Ruleset) -> Shape {
+        return counter_shape(rules, most_common(&self.counts));
+    }
+
+    fn observe(&mut self, opponent_shape: Shape) {
+        if self.counts.len() <= opponent_shape.0 {
+            self.counts.resize(opponent_shape.0 + 1, 0);
+        }
+        self.counts[opponent_shape.0] += 1;
+    }
+}
+
+// Predicts the opponent's next shape from the shapes that followed their last `order` shapes,
+// falling back to overall frequencies until that context has been seen before
+pub struct MarkovPredictor {
+    order: usize,
+    history: Vec<Shape>,
+    transitions: HashMap<Vec<Shape>, Vec<usize>>,
+    fallback: FrequencyCounter,
+}
+
+impl MarkovPredictor {
+    pub fn new(order: usize) -> Self {
+        return MarkovPredictor {
+            order,
+            history: Vec::new(),
+            transitions: HashMap::new(),
+            fallback: FrequencyCounter::new(),
+        };
+    }
+
+    fn context(&self) -> Option<&[Shape]> {
+        if self.history.len() < self.order {
+            return None;
+        }
+        return Some(&self.history[self.history.len() - self.order..]);
+    }
+}
+
+impl Strategy for MarkovPredictor {
+    fn name(&self, _rules: &Ruleset) -> String {
+        return format!("Markov predictor (order {})", self.order);
+    }
+
+    fn choose_shape(&mut self, rules: &Ruleset) -> Shape {
+        let predicted = self
+            .context()
+            .and_then(|c| self.transitions.get(c))
+            .and_then(|counts| most_common(counts));
+
+        return match predicted {
+            Some(_) => counter_shape(rules, predicted),
+            None => self.fallback.choose_shape(rules),
+        };
+    }
+
+    fn observe(&mut self, opponent_shape: Shape) {
+        if let Some(context) = self.context() {
+            let counts = self.transitions.entry(context.to_vec()).or_default();
+            if counts.len() <= opponent_shape.0 {
+                counts.resize(opponent_shape.0 + 1, 0);
+            }
+            counts[opponent_shape.0] += 1;
+        }
+
+        self.history.push(opponent_shape);
+        self.fallback.observe(opponent_shape);
+    }
+}
+
+pub fn simulate(
+    path: &Path,
+    rules: &Ruleset,
+    mode: ParseMode,
+    strategies: &mut [Box<dyn Strategy>],
+    part_1_score: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut guide = GuideReader::open(path, mode)?;
+    let mut opponent_shapes = Vec::new();
+
+    // The player shapes are not used, but they are still checked so that exactly the same rounds
+    // are scored as in part 1, which the strategies are compared against
+    while let Some((_, opponent_shape)) = guide.next_round(|a, b| {
+        let opponent_shape = a.parse_with(|s| rules.parse_opponent_shape(s))?;
+        b.parse_with(|s| rules.parse_player_shape(s))?;
+        return Ok(opponent_shape);
+    })? {
+        opponent_shapes.push(opponent_shape);
+    }
+    guide.report_skipped("Simulate");
+
+    let mut results = Vec::with_capacity(strategies.len());
+    for strategy in strategies.iter_mut() {
+        let mut score = 0;
+
+        for opponent_shape in opponent_shapes.iter() {
+            let player_shape = strategy.choose_shape(rules);
+            score += rules.shape_score(player_shape);
+            score += rules.outcome(player_shape, *opponent_shape) as usize;
+            strategy.observe(*opponent_shape);
+        }
+        results.push((strategy.name(rules), score));
+    }
+
+    // Knowing the opponent's shape in advance, the best reply is whichever shape scores the most
+    // points against it, counting both the shape and the outcome
+    let perfect_score: usize = opponent_shapes
+        .iter()
+        .map(|opponent_shape| {
+            return (0..rules.num_shapes())
+                .map(|i| {
+                    let shape = Shape(i);
+                    return rules.shape_score(shape)
+                        + rules.outcome(shape, *opponent_shape) as usize;
+                })
+                .max()
+                .unwrap_or(0);
+        })
+        .sum();
+
+    let width = results.iter().map(|r| r.0.len()).max().unwrap_or(0).max(26);
+    println!("{:<width$}  {:>8}  {:>9}", "Strategy", "Score", "vs Part 1");
+    println!(
+        "{:<width$}  {:>8}  {:>9}",
+        "Perfect information", perfect_score, ""
+    );
+    println!(
+        "{:<width$}  {:>8}  {:>9}",
+        "Part 1 strategy guide", part_1_score, ""
+    );
+
+    for (name, score) in results.iter() {
+        let difference = *score as isize - part_1_score as isize;
+        println!("{name:<width$}  {score:>8}  {difference:>+9}");
+    }
+    return Ok(());
+}
@@ -0,0 +1,216 @@
+use std::cmp::Ordering;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::guide::{GuideReader, ParseMode};
+use crate::rules::Ruleset;
+use crate::{Outcome, Shape};
+
+// How the second column of each player's guide is turned into the shapes they play
+#[derive(Clone, Copy)]
+pub enum Reading {
+    Shape,
+    Outcome,
+}
+
+impl FromStr for Reading {
+    type Err = std::io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "shape" => Ok(Reading::Shape),
+            "outcome" => Ok(Reading::Outcome),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Reading must only be shape or outcome",
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum TieBreaker {
+    Wins,
+    Draws,
+    Losses,
+    Name,
+}
+
+impl TieBreaker {
+    fn compare(&self, a: &Player, b: &Player) -> Ordering {
+        return match self {
+            TieBreaker::Wins => b.wins.cmp(&a.wins),
+            TieBreaker::Draws => b.draws.cmp(&a.draws),
+            TieBreaker::Losses => a.losses.cmp(&b.losses),
+            TieBreaker::Name => a.name.cmp(&b.name),
+        };
+    }
+}
+
+impl FromStr for TieBreaker {
+    type Err = std::io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wins" => Ok(TieBreaker::Wins),
+            "draws" => Ok(TieBreaker::Draws),
+            "losses" => Ok(TieBreaker::Losses),
+            "name" => Ok(TieBreaker::Name),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Tie-breakers must only be wins, draws, losses, or name",
+            )),
+        }
+    }
+}
+
+pub struct TournamentOptions {
+    pub rounds: Option<usize>,
+    pub reading: Reading,
+    pub tie_breakers: Vec<TieBreaker>,
+}
+
+impl Default for TournamentOptions {
+    fn default() -> Self {
+        return TournamentOptions {
+            rounds: None,
+            reading: Reading::Shape,
+            tie_breakers: vec![TieBreaker::Wins, TieBreaker::Losses],
+        };
+    }
+}
+
+struct Player {
+    name: String,
+    moves: Vec<Shape>,
+    wins: usize,
+    draws: usize,
+    losses: usize,
+    points: usize,
+}
+
+impl Player {
+    fn from_file(
+        path: &Path,
+        rules: &Ruleset,
+        mode: ParseMode,
+        reading: Reading,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut guide = GuideReader::open(path, mode)?;
+        let mut moves = Vec::new();
+
+        while let Some((_, shape)) = guide.next_round(|a, b| {
+            let opponent_shape = a.parse_with(|s| rules.parse_opponent_shape(s))?;
+            return match reading {
+                Reading::Shape => b.parse_with(|s| rules.parse_player_shape(s)),
+                Reading::Outcome => {
+                    let outcome = b.parse_with(|s| rules.parse_outcome(s))?;
+                    Ok(rules.shape_for_outcome(opponent_shape, outcome))
+                }
+            };
+        })? {
+            moves.push(shape);
+        }
+        guide.report_skipped(&path.display().to_string());
+
+        if moves.is_empty() {
+            return Err(format!("Strategy guide {path:?} contains no rounds"))?;
+        }
+
+        let name = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        return Ok(Player {
+            name,
+            moves,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+            points: 0,
+        });
+    }
+
+    fn get_move(&self, round: usize) -> Shape {
+        return self.moves[round % self.moves.len()];
+    }
+
+    fn record_round(&mut self, rules: &Ruleset, shape: Shape, outcome: Outcome) {
+        self.points += rules.shape_score(shape) + outcome as usize;
+        match outcome {
+            Outcome::Lose => self.losses += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Win => self.wins += 1,
+        }
+    }
+}
+
+pub fn run_tournament(
+    paths: &[String],
+    rules: &Ruleset,
+    mode: ParseMode,
+    options: &TournamentOptions,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if paths.len() < 2 {
+        return Err("A tournament needs at least two strategy guides")?;
+    }
+
+    let mut players = Vec::with_capacity(paths.len());
+    for path in paths {
+        players.push(Player::from_file(
+            Path::new(path),
+            rules,
+            mode,
+            options.reading,
+        )?);
+    }
+
+    // Players whose guides are shorter than the number of rounds repeat their guide from the start
+    let rounds = options
+        .rounds
+        .unwrap_or_else(|| players.iter().map(|p| p.moves.len()).min().unwrap_or(0));
+
+    for a in 0..players.len() {
+        for b in (a + 1)..players.len() {
+            for round in 0..rounds {
+                let shape_a = players[a].get_move(round);
+                let shape_b = players[b].get_move(round);
+                players[a].record_round(rules, shape_a, rules.outcome(shape_a, shape_b));
+                players[b].record_round(rules, shape_b, rules.outcome(shape_b, shape_a));
+            }
+        }
+    }
+
+    players.sort_by(|a, b| {
+        let mut ordering = b.points.cmp(&a.points);
+        for tie_breaker in options.tie_breakers.iter() {
+            ordering = ordering.then_with(|| tie_breaker.compare(a, b));
+        }
+        return ordering.then_with(|| TieBreaker::Name.compare(a, b));
+    });
+
+    let width = players
+        .iter()
+        .map(|p| p.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Player".len());
+    println!("{rounds} rounds per match");
+    println!(
+        "{:>4}  {:<width$}  {:>6}  {:>6}  {:>6}  {:>8}",
+        "Rank", "Player", "Wins", "Draws", "Losses", "Points"
+    );
+
+    for (rank, p) in players.iter().enumerate() {
+        println!(
+            "{:>4}  {:<width$}  {:>6}  {:>6}  {:>6}  {:>8}",
+            rank + 1,
+            p.name,
+            p.wins,
+            p.draws,
+            p.losses,
+            p.points
+        );
+    }
+    return Ok(());
+}
@@ -0,0 +1,146 @@
+use std::io::Write;
+use std::str::FromStr;
+
+use crate::rules::Ruleset;
+use crate::{Outcome, Shape};
+
+#[derive(Clone, Copy)]
+pub enum TraceFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for TraceFormat {
+    type Err = std::io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(TraceFormat::Csv),
+            "json" => Ok(TraceFormat::Json),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "Trace format must only be csv or json",
+            )),
+        }
+    }
+}
+
+struct TraceRow {
+    part: usize,
+    line_num: usize,
+    opponent_shape: String,
+    player_shape: String,
+    outcome: Outcome,
+    shape_points: usize,
+    outcome_points: usize,
+    running_total: usize,
+}
+
+pub struct Trace {
+    part: usize,
+    rows: Vec<TraceRow>,
+}
+
+impl Trace {
+    pub fn new() -> Self {
+        return Trace {
+            part: 0,
+            rows: Vec::new(),
+        };
+    }
+
+    pub fn start_part(&mut self, part: usize) {
+        self.part = part;
+    }
+
+    pub fn record(
+        &mut self,
+        rules: &Ruleset,
+        line_num: usize,
+        opponent_shape: Shape,
+        player_shape: Shape,
+        outcome: Outcome,
+    ) {
+        let previous_total = match self.rows.last() {
+            Some(row) if row.part == self.part => row.running_total,
+            _ => 0,
+        };
+        let shape_points = rules.shape_score(player_shape);
+        let outcome_points = outcome as usize;
+
+        self.rows.push(TraceRow {
+            part: self.part,
+            line_num,
+            opponent_shape: rules.shape_name(opponent_shape).to_string(),
+            player_shape: rules.shape_name(player_shape).to_string(),
+            outcome,
+            shape_points,
+            outcome_points,
+            running_total: previous_total + shape_points + outcome_points,
+        });
+    }
+
+    pub fn write(&self, format: TraceFormat, out: &mut impl Write) -> Result<(), std::io::Error> {
+        match format {
+            TraceFormat::Csv => {
+                writeln!(out, "part,line,opponent_shape,player_shape,outcome,shape_points,outcome_points,running_total")?;
+                for r in self.rows.iter() {
+                    writeln!(
+                        out,
+                        "{},{},{},{},{:?},{},{},{}",
+                        r.part,
+                        r.line_num,
+                        csv_field(&r.opponent_shape),
+                        csv_field(&r.player_shape),
+                        r.outcome,
+                        r.shape_points,
+                        r.outcome_points,
+                        r.running_total
+                    )?;
+                }
+            }
+            TraceFormat::Json => {
+                writeln!(out, "[")?;
+                for (i, r) in self.rows.iter().enumerate() {
+                    let separator = if i + 1 < self.rows.len() { "," } else { "" };
+                    writeln!(
+                        out,
+                        "  {{\"part\": {}, \"line\": {}, \"opponent_shape\": {}, \"player_shape\": {}, \"outcome\": \"{:?}\", \"shape_points\": {}, \"outcome_points\": {}, \"running_total\": {}}}{separator}",
+                        r.part,
+                        r.line_num,
+                        json_string(&r.opponent_shape),
+                        json_string(&r.player_shape),
+                        r.outcome,
+                        r.shape_points,
+                        r.outcome_points,
+                        r.running_total
+                    )?;
+                }
+                writeln!(out, "]")?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"']) {
+        return format!("\"{}\"", s.replace('"', "\"\""));
+    }
+    return s.to_string();
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    return escaped;
+}
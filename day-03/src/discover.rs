@@ -0,0 +1,255 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::priority::PriorityTable;
+use crate::ItemSet;
+
+struct CandidateGroup {
+    members: Vec<usize>,
+    badge: usize,
+}
+
+enum Partition {
+    Unique(Vec<usize>),
+    Ambiguous(Vec<usize>, Vec<usize>),
+    Impossible,
+    Undetermined,
+}
+
+struct Search<'a> {
+    groups: &'a [CandidateGroup],
+    groups_by_member: Vec<Vec<usize>>,
+    assigned: Vec<bool>,
+    chosen: Vec<usize>,
+    solutions: Vec<Vec<usize>>,
+    nodes: usize,
+    node_limit: usize,
+}
+
+impl Search<'_> {
+    fn is_available(&self, group: usize) -> bool {
+        return self.groups[group]
+            .members
+            .iter()
+            .all(|m| !self.assigned[*m]);
+    }
+
+    // Returns false once the search should stop, either because two partitions have been found
+    // or because the node limit was reached
+    fn search(&mut self) -> bool {
+        self.nodes += 1;
+        if self.nodes > self.node_limit {
+            return false;
+        }
+
+        // Branch on the unassigned rucksack with the fewest groups it could still join
+        let mut best: Option<(usize, usize)> = None;
+        for rucksack in 0..self.assigned.len() {
+            if self.assigned[rucksack] {
+                continue;
+            }
+
+            let options = self.groups_by_member[rucksack]
+                .iter()
+                .filter(|g| self.is_available(**g))
+                .count();
+            if best.is_none_or(|(_, n)| options < n) {
+                best = Some((rucksack, options));
+            }
+            if options == 0 {
+                return true;
+            }
+        }
+
+        let rucksack = match best {
+            Some((rucksack, _)) => rucksack,
+            None => {
+                self.solutions.push(self.chosen.clone());
+                return self.solutions.len() < 2;
+            }
+        };
+
+        for i in 0..self.groups_by_member[rucksack].len() {
+            let group = self.groups_by_member[rucksack][i];
+            if !self.is_available(group) {
+                continue;
+            }
+
+            for m in self.groups[group].members.iter() {
+                self.assigned[*m] = true;
+            }
+            self.chosen.push(group);
+            let keep_going = self.search();
+            self.chosen.pop();
+            for m in self.groups[group].members.iter() {
+                self.assigned[*m] = false;
+            }
+
+            if !keep_going {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+// Collects every group of rucksacks with exactly one item in common. Each rucksack added to a
+// partial group counts as a step against the same limit as the search, since the number of
+// groups to try grows quickly with the group size
+struct CandidateFinder<'a> {
+    rucksacks: &'a [ItemSet],
+    group_size: usize,
+    members: Vec<usize>,
+    groups: Vec<CandidateGroup>,
+    steps: usize,
+    step_limit: usize,
+}
+
+impl CandidateFinder<'_> {
+    // Returns false once the step limit is reached
+    fn find(&mut self, first: usize, common_items: &ItemSet) -> bool {
+        self.steps += 1;
+        if self.steps > self.step_limit {
+            return false;
+        }
+
+        if self.members.len() == self.group_size {
+            let mut indices = common_items.indices();
+            if let (Some(badge), 0) = (indices.next(), indices.len()) {
+                self.groups.push(CandidateGroup {
+                    members: self.members.clone(),
+                    badge,
+                });
+            }
+            return true;
+        }
+
+        for r in first..self.rucksacks.len() {
+            let common = common_items & &self.rucksacks[r];
+            if common.indices().len() == 0 {
+                continue;
+            }
+
+            self.members.push(r);
+            let keep_going = self.find(r + 1, &common);
+            self.members.pop();
+
+            if !keep_going {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+fn describe_partition(groups: &[CandidateGroup], chosen: &[usize], table: &PriorityTable) {
+    let mut chosen = chosen.to_vec();
+    chosen.sort_by_key(|g| groups[*g].members[0]);
+    let mut priority_sum = 0;
+
+    for g in chosen.iter() {
+        let group = &groups[*g];
+        let lines: Vec<String> = group.members.iter().map(|m| (m + 1).to_string()).collect();
+        priority_sum += table.priority(group.badge);
+        println!(
+            "  Lines {}: badge {}",
+            lines.join(", "),
+            table.item(group.badge)
+        );
+    }
+    println!("  Badge priority sum: {priority_sum}");
+}
+
+pub fn discover_groups(
+    path: &Path,
+    table: &PriorityTable,
+    group_size: usize,
+    node_limit: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if group_size == 0 {
+        return Err("Group size must be at least 1")?;
+    }
+
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let mut rucksacks = Vec::new();
+
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let items =
+            ItemSet::from_items(line?.trim(), table).map_err(|e| format!("Line {}: {e}", i + 1))?;
+        rucksacks.push(items);
+    }
+
+    let mut finder = CandidateFinder {
+        rucksacks: &rucksacks,
+        group_size,
+        members: Vec::with_capacity(group_size),
+        groups: Vec::new(),
+        steps: 0,
+        step_limit: node_limit,
+    };
+    let candidates_found =
+        !rucksacks.len().is_multiple_of(group_size) || finder.find(0, &ItemSet::full(table.len()));
+    let CandidateFinder { groups, steps, .. } = finder;
+    let mut groups_by_member = vec![Vec::new(); rucksacks.len()];
+
+    for (g, group) in groups.iter().enumerate() {
+        for m in group.members.iter() {
+            groups_by_member[*m].push(g);
+        }
+    }
+
+    let mut search = Search {
+        groups: &groups,
+        groups_by_member,
+        assigned: vec![false; rucksacks.len()],
+        chosen: Vec::new(),
+        solutions: Vec::new(),
+        nodes: steps,
+        node_limit,
+    };
+    let finished = candidates_found && (search.search() || search.solutions.len() == 2);
+
+    let mut solutions = search.solutions.into_iter();
+    let partition = match (solutions.next(), solutions.next()) {
+        (Some(a), Some(b)) => Partition::Ambiguous(a, b),
+        (Some(a), None) if finished => Partition::Unique(a),
+        (None, None) if finished => Partition::Impossible,
+        _ => Partition::Undetermined,
+    };
+
+    if candidates_found {
+        println!(
+            "{} rucksacks, {} candidate groups of {group_size} with exactly one common item",
+            rucksacks.len(),
+            groups.len()
+        );
+    } else {
+        println!(
+            "{} rucksacks, at least {} candidate groups of {group_size} with exactly one common \
+             item found before the search limit",
+            rucksacks.len(),
+            groups.len()
+        );
+    }
+
+    match partition {
+        Partition::Unique(chosen) => {
+            println!("Unique partition:");
+            describe_partition(&groups, &chosen, table);
+        }
+        Partition::Ambiguous(first, second) => {
+            println!("Ambiguous, at least two partitions exist. First partition:");
+            describe_partition(&groups, &first, table);
+            println!("Second partition:");
+            describe_partition(&groups, &second, table);
+        }
+        Partition::Impossible => {
+            println!("Impossible, no partition into groups with a single badge exists");
+        }
+        Partition::Undetermined => {
+            println!("Undetermined, the search stopped after {node_limit} steps");
+        }
+    }
+    return Ok(());
+}
@@ -1,79 +1,302 @@
+mod discover;
+mod priority;
+mod report;
+
+use priority::PriorityTable;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
+use std::ops::{BitAnd, BitAndAssign, BitOr, Sub};
+use std::path::Path;
+
+// Splits a rucksack into its two compartments by item count rather than byte length, so
+// multi-byte items are never cut in half
+fn split_compartments(rucksack: &str) -> (&str, &str) {
+    let num_items = rucksack.chars().count();
+    let split_index = rucksack
+        .char_indices()
+        .nth(num_items / 2)
+        .map_or(rucksack.len(), |c| c.0);
+    return rucksack.split_at(split_index);
+}
+
+// Set of items stored as a bit mask, where each item's bit is its index in the priority table
+#[derive(Clone, PartialEq, Eq)]
+struct ItemSet {
+    words: Vec<u64>,
+}
+
+impl ItemSet {
+    fn empty(num_items: usize) -> ItemSet {
+        return ItemSet {
+            words: vec![0; num_items.div_ceil(64)],
+        };
+    }
+
+    fn full(num_items: usize) -> ItemSet {
+        let mut set = ItemSet {
+            words: vec![u64::MAX; num_items.div_ceil(64)],
+        };
+        if !num_items.is_multiple_of(64) {
+            if let Some(last) = set.words.last_mut() {
+                *last = (1 << (num_items % 64)) - 1;
+            }
+        }
+        return set;
+    }
+
+    fn from_items(items: &str, table: &PriorityTable) -> Result<ItemSet, String> {
+        let mut set = ItemSet::empty(table.len());
+        for item in items.chars() {
+            set.insert(table.index(item)?);
+        }
+        return Ok(set);
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.words[index / 64] |= 1 << (index % 64);
+    }
+
+    // Iterates over the table indices of the items in the set, from lowest to highest
+    fn indices(&self) -> ItemSetIndices<'_> {
+        return ItemSetIndices {
+            words: &self.words,
+            word_index: 0,
+            current: self.words.first().copied().unwrap_or(0),
+            remaining: self.words.iter().map(|w| w.count_ones() as usize).sum(),
+        };
+    }
+}
+
+struct ItemSetIndices<'a> {
+    words: &'a [u64],
+    word_index: usize,
+    current: u64,
+    remaining: usize,
+}
+
+impl Iterator for ItemSetIndices<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.word_index += 1;
+            if self.word_index >= self.words.len() {
+                return None;
+            }
+            self.current = self.words[self.word_index];
+        }
+
+        let index = self.word_index * 64 + self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+        self.remaining -= 1;
+        return Some(index);
+    }
 
-fn get_item_priority(item: char) -> Result<usize, &'static str> {
-    if item.is_ascii_lowercase() {
-        return Ok((item as usize) - (b'a' as usize) + 1);
-    } else if item.is_ascii_uppercase() {
-        return Ok((item as usize) - (b'A' as usize) + 27);
-    } else {
-        return Err("Items must be uppercase or lowercase ASCII characters");
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        return (self.remaining, Some(self.remaining));
+    }
+}
+
+impl ExactSizeIterator for ItemSetIndices<'_> {}
+
+impl BitAnd for &ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: Self) -> ItemSet {
+        let words = self.words.iter().zip(other.words.iter());
+        return ItemSet {
+            words: words.map(|(a, b)| a & b).collect(),
+        };
+    }
+}
+
+impl BitAndAssign<&ItemSet> for ItemSet {
+    fn bitand_assign(&mut self, other: &ItemSet) {
+        for (a, b) in self.words.iter_mut().zip(other.words.iter()) {
+            *a &= b;
+        }
     }
 }
 
-fn solution_part_1() -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
+impl BitOr for &ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: Self) -> ItemSet {
+        let words = self.words.iter().zip(other.words.iter());
+        return ItemSet {
+            words: words.map(|(a, b)| a | b).collect(),
+        };
+    }
+}
+
+impl Sub for &ItemSet {
+    type Output = ItemSet;
+
+    fn sub(self, other: Self) -> ItemSet {
+        let words = self.words.iter().zip(other.words.iter());
+        return ItemSet {
+            words: words.map(|(a, b)| a & !b).collect(),
+        };
+    }
+}
+
+fn solution_part_1(
+    path: &Path,
+    table: &PriorityTable,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
     let mut reader = BufReader::new(file);
     let mut line = String::with_capacity(8);
     let mut priority_sum = 0;
 
-    'outer: while reader.read_line(&mut line)? != 0 {
+    while reader.read_line(&mut line)? != 0 {
         let l = line.trim();
-        let compartments = l.split_at(l.len() / 2);
-
-        for c0 in compartments.0.chars() {
-            for c1 in compartments.1.chars() {
-                if c0 == c1 {
-                    priority_sum += get_item_priority(c1)?;
-                    line.clear();
-                    continue 'outer;
-                }
-            }
-        }
-        return Err("No common item found")?;
+        let compartments = split_compartments(l);
+        let common_items = &ItemSet::from_items(compartments.0, table)?
+            & &ItemSet::from_items(compartments.1, table)?;
+        let common_item = common_items
+            .indices()
+            .next()
+            .ok_or("No common item found")?;
+
+        priority_sum += table.priority(common_item);
+        line.clear();
     }
     return Ok(priority_sum);
 }
 
-fn solution_part_2() -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
+struct BadgeGroup {
+    first_line: usize,
+    num_rucksacks: usize,
+    common_items: ItemSet,
+}
+
+impl BadgeGroup {
+    fn describe(&self, table: &PriorityTable) -> String {
+        let items: String = self.common_items.indices().map(|i| table.item(i)).collect();
+        let last_line = self.first_line + self.num_rucksacks - 1;
+
+        return match self.common_items.indices().len() {
+            0 => format!("Lines {}-{last_line}: no common item", self.first_line),
+            n => format!(
+                "Lines {}-{last_line}: {n} common items ({items})",
+                self.first_line
+            ),
+        };
+    }
+}
+
+// Groups without exactly one common item don't count towards the sum and are returned instead
+fn solution_part_2(
+    path: &Path,
+    table: &PriorityTable,
+    group_size: usize,
+    allow_partial: bool,
+) -> Result<(usize, Vec<BadgeGroup>), Box<dyn std::error::Error>> {
+    if group_size == 0 {
+        return Err("Group size must be at least 1")?;
+    }
+
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
     let mut lines = BufReader::new(file).lines().peekable();
     let mut priority_sum = 0;
+    let mut unmatched_groups = Vec::new();
+    let mut line_num = 0;
 
-    'outer: while lines.peek().is_some() {
-        let mut rucksacks: [String; 3] = Default::default();
+    while lines.peek().is_some() {
+        let mut group = BadgeGroup {
+            first_line: line_num + 1,
+            num_rucksacks: 0,
+            common_items: ItemSet::full(table.len()),
+        };
 
-        for rucksack in rucksacks.iter_mut() {
+        while group.num_rucksacks < group_size {
             if let Some(line) = lines.next() {
-                *rucksack = line?;
+                group.common_items &= &ItemSet::from_items(line?.trim(), table)?;
+                group.num_rucksacks += 1;
+                line_num += 1;
+            } else if allow_partial {
+                break;
             } else {
                 return Result::Err(std::io::Error::new(
                     std::io::ErrorKind::NotFound,
-                    "Not enough items in rucksack",
+                    format!(
+                        "Final group only has {} of {group_size} rucksacks",
+                        group.num_rucksacks
+                    ),
                 ))?;
             }
         }
 
-        for item_1 in rucksacks[0].chars() {
-            for item_2 in rucksacks[1].chars() {
-                for item_3 in rucksacks[2].chars() {
-                    if item_1 == item_2 && item_1 == item_3 {
-                        priority_sum += get_item_priority(item_1)?;
-                        continue 'outer;
-                    }
-                }
-            }
+        let mut indices = group.common_items.indices();
+        match (indices.next(), indices.len()) {
+            (Some(index), 0) => priority_sum += table.priority(index),
+            _ => unmatched_groups.push(group),
         }
-        return Err("No common item found")?;
     }
-    return Ok(priority_sum);
+    return Ok((priority_sum, unmatched_groups));
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let part_1 = solution_part_1()?;
-    let part_2 = solution_part_2()?;
+    let mut args = std::env::args().skip(1);
+    let mut input = String::from("input.txt");
+    let mut group_size = 3;
+    let mut allow_partial = false;
+    let mut report = false;
+    let mut table = PriorityTable::letters();
+    let mut discover = false;
+    let mut search_limit = 10_000_000;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--group-size" => {
+                group_size = args
+                    .next()
+                    .ok_or("--group-size requires a value")?
+                    .parse()?;
+            }
+            "--allow-partial" => allow_partial = true,
+            "--report" => report = true,
+            "--discover" => discover = true,
+            "--search-limit" => {
+                search_limit = args
+                    .next()
+                    .ok_or("--search-limit requires a value")?
+                    .parse()?;
+            }
+            "--priorities" => {
+                let name = args
+                    .next()
+                    .ok_or("--priorities requires a preset name or file path")?;
+                table = match PriorityTable::from_preset(&name) {
+                    Some(preset) => preset,
+                    None => PriorityTable::from_file(Path::new(&name))?,
+                };
+            }
+            _ => input = arg,
+        }
+    }
+
+    if report {
+        return report::duplicate_report(Path::new(&input), &table);
+    }
+
+    if discover {
+        return discover::discover_groups(Path::new(&input), &table, group_size, search_limit);
+    }
+
+    let part_1 = solution_part_1(Path::new(&input), &table)?;
+    let (part_2, unmatched_groups) =
+        solution_part_2(Path::new(&input), &table, group_size, allow_partial)?;
 
     println!("Part 1 - Duplicate item priority sum: {part_1}");
     println!("Part 2 - Badge priority sum: {part_2}");
+
+    if !unmatched_groups.is_empty() {
+        println!("Groups without a single badge:");
+        for group in unmatched_groups.iter() {
+            println!("  {}", group.describe(&table));
+        }
+    }
     return Ok(());
 }
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+// Maps each item in an alphabet to a dense index, used as its bit in an ItemSet, and a priority
+pub struct PriorityTable {
+    indices: HashMap<char, usize>,
+    items: Vec<char>,
+    priorities: Vec<usize>,
+}
+
+impl PriorityTable {
+    fn new() -> Self {
+        return PriorityTable {
+            indices: HashMap::new(),
+            items: Vec::new(),
+            priorities: Vec::new(),
+        };
+    }
+
+    fn push(&mut self, item: char, priority: usize) -> Result<(), String> {
+        if self.indices.contains_key(&item) {
+            return Err(format!("Item {item:?} appears more than once"));
+        }
+
+        self.indices.insert(item, self.items.len());
+        self.items.push(item);
+        self.priorities.push(priority);
+        return Ok(());
+    }
+
+    fn push_sequence(&mut self, items: impl Iterator<Item = char>) {
+        for item in items {
+            let priority = self.items.len() + 1;
+            self.push(item, priority)
+                .expect("Built-in priority tables should not repeat items");
+        }
+    }
+
+    // a-z are 1-26 and A-Z are 27-52, as in the puzzle
+    pub fn letters() -> Self {
+        let mut table = PriorityTable::new();
+        table.push_sequence(('a'..='z').chain('A'..='Z'));
+        return table;
+    }
+
+    pub fn from_preset(name: &str) -> Option<Self> {
+        let mut table = PriorityTable::new();
+        match name {
+            "letters" => return Some(PriorityTable::letters()),
+            "digits" => table.push_sequence('0'..='9'),
+            "alphanumeric" => table.push_sequence(('a'..='z').chain('A'..='Z').chain('0'..='9')),
+            // ASCII letters followed by the letters of the Latin-1, Latin Extended, Greek and
+            // Cyrillic blocks
+            "unicode" => table.push_sequence(
+                ('a'..='z')
+                    .chain('A'..='Z')
+                    .chain(('\u{00C0}'..='\u{04FF}').filter(|c| c.is_alphabetic())),
+            ),
+            _ => return None,
+        }
+        return Some(table);
+    }
+
+    // Each non-empty line holds an item and its priority, separated by whitespace
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+        let mut table = PriorityTable::new();
+
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            let mut tokens = line.split_whitespace();
+            let line_num = i + 1;
+
+            let item = match tokens.next() {
+                None => continue,
+                Some(t) if t.starts_with('#') => continue,
+                Some(t) => {
+                    let mut chars = t.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => c,
+                        _ => return Err(format!("Line {line_num}: items must be one character"))?,
+                    }
+                }
+            };
+            let priority = tokens
+                .next()
+                .ok_or(format!("Line {line_num}: missing priority"))?
+                .parse::<usize>()
+                .map_err(|e| format!("Line {line_num}: invalid priority: {e}"))?;
+
+            table
+                .push(item, priority)
+                .map_err(|e| format!("Line {line_num}: {e}"))?;
+        }
+
+        if table.items.is_empty() {
+            return Err(format!("Priority table {path:?} contains no items"))?;
+        }
+        return Ok(table);
+    }
+
+    pub fn len(&self) -> usize {
+        return self.items.len();
+    }
+
+    pub fn index(&self, item: char) -> Result<usize, String> {
+        return self
+            .indices
+            .get(&item)
+            .copied()
+            .ok_or_else(|| format!("Item {item:?} is not in the priority table"));
+    }
+
+    pub fn item(&self, index: usize) -> char {
+        return self.items[index];
+    }
+
+    pub fn priority(&self, index: usize) -> usize {
+        return self.priorities[index];
+    }
+}
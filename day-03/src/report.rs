@@ -0,0 +1,93 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::priority::PriorityTable;
+use crate::{split_compartments, ItemSet};
+
+fn positions(items: &str, offset: usize, item: char) -> Vec<usize> {
+    return items
+        .chars()
+        .enumerate()
+        .filter(|(_, c)| *c == item)
+        .map(|(i, _)| offset + i + 1)
+        .collect();
+}
+
+fn format_positions(positions: &[usize]) -> String {
+    let positions: Vec<String> = positions.iter().map(|p| p.to_string()).collect();
+    return positions.join(",");
+}
+
+pub fn duplicate_report(
+    path: &Path,
+    table: &PriorityTable,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let mut reader = BufReader::new(file);
+    let mut line = String::with_capacity(8);
+    let mut line_num = 0;
+    // Indexed by item: the number of rucksacks the item was duplicated in
+    let mut duplicate_counts = vec![0; table.len()];
+
+    while reader.read_line(&mut line)? != 0 {
+        line_num += 1;
+        let l = line.trim();
+        let num_items = l.chars().count();
+
+        if !num_items.is_multiple_of(2) {
+            println!("Line {line_num}: odd number of items ({num_items}), can't be split evenly");
+            line.clear();
+            continue;
+        }
+
+        let compartments = split_compartments(l);
+        let first_items = ItemSet::from_items(compartments.0, table)
+            .map_err(|e| format!("Line {line_num}: {e}"))?;
+        let second_items = ItemSet::from_items(compartments.1, table)
+            .map_err(|e| format!("Line {line_num}: {e}"))?;
+        let common_items = &first_items & &second_items;
+
+        println!(
+            "Line {line_num}: {} duplicate items",
+            common_items.indices().len()
+        );
+
+        for index in common_items.indices() {
+            let item = table.item(index);
+            let priority = table.priority(index);
+            let first_positions = positions(compartments.0, 0, item);
+            let second_positions = positions(compartments.1, num_items / 2, item);
+            duplicate_counts[index] += 1;
+
+            println!(
+                "  {item} (priority {priority}): first compartment x{} at {}, second compartment x{} at {}",
+                first_positions.len(),
+                format_positions(&first_positions),
+                second_positions.len(),
+                format_positions(&second_positions)
+            );
+        }
+        line.clear();
+    }
+
+    println!("Summary by item:");
+    let mut priority_total = 0;
+
+    for (index, count) in duplicate_counts.iter().enumerate() {
+        if *count == 0 {
+            continue;
+        }
+
+        let item = table.item(index);
+        let priority = table.priority(index);
+        priority_total += priority * count;
+        println!(
+            "  {item} (priority {priority}): duplicated in {count} rucksacks, priority total {}",
+            priority * count
+        );
+    }
+
+    println!("Total duplicate priority: {priority_total}");
+    return Ok(());
+}
@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+
+use crate::interval::{Assignment, IntervalSet};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CoverageSegment {
+    pub sections: Assignment,
+    pub depth: usize,
+}
+
+// Number of assignments covering each section between the lowest and highest assigned section,
+// stored as runs of sections with the same depth
+pub struct Coverage {
+    segments: Vec<CoverageSegment>,
+}
+
+impl Coverage {
+    pub fn from_assignments(assignments: impl IntoIterator<Item = Assignment>) -> Self {
+        // Each assignment stops covering the section after its end, which is one past usize::MAX
+        // for an assignment ending on the last section, so positions are widened to u128
+        let mut events = Vec::new();
+        for a in assignments.into_iter().filter(|a| !a.is_empty()) {
+            events.push((a.section_start as u128, 1isize));
+            events.push((a.section_end as u128 + 1, -1isize));
+        }
+        events.sort_unstable();
+
+        let mut segments: Vec<CoverageSegment> = Vec::new();
+        let mut depth = 0isize;
+        let mut i = 0;
+
+        while i < events.len() {
+            let position = events[i].0;
+            while i < events.len() && events[i].0 == position {
+                depth += events[i].1;
+                i += 1;
+            }
+
+            if let Some(&(next_position, _)) = events.get(i) {
+                // Both fit in a usize as there is an event after this position
+                let segment = CoverageSegment {
+                    sections: Assignment::new(position as usize, (next_position - 1) as usize),
+                    depth: depth as usize,
+                };
+
+                match segments.last_mut() {
+                    Some(last) if last.depth == segment.depth => {
+                        last.sections.section_end = segment.sections.section_end;
+                    }
+                    _ => segments.push(segment),
+                }
+            }
+        }
+        return Coverage { segments };
+    }
+
+    pub fn segments(&self) -> &[CoverageSegment] {
+        return &self.segments;
+    }
+
+    pub fn span(&self) -> Option<Assignment> {
+        let first = self.segments.first()?;
+        let last = self.segments.last()?;
+        return Some(Assignment::new(
+            first.sections.section_start,
+            last.sections.section_end,
+        ));
+    }
+
+    pub fn uncovered(&self) -> IntervalSet {
+        return self.sections_where(|depth| depth == 0);
+    }
+
+    pub fn covered_more_than(&self, threshold: usize) -> IntervalSet {
+        return self.sections_where(|depth| depth > threshold);
+    }
+
+    // The deepest coverage and every section where it is reached
+    pub fn max_depth(&self) -> (usize, IntervalSet) {
+        let max_depth = self.segments.iter().map(|s| s.depth).max().unwrap_or(0);
+        return (max_depth, self.sections_where(|depth| depth == max_depth));
+    }
+
+    // Number of sections covered by each depth
+    pub fn histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for segment in self.segments.iter() {
+            let sections = histogram.entry(segment.depth).or_insert(0usize);
+            *sections = sections.saturating_add(segment.sections.len());
+        }
+        return histogram;
+    }
+
+    fn sections_where(&self, predicate: impl Fn(usize) -> bool) -> IntervalSet {
+        return self
+            .segments
+            .iter()
+            .filter(|s| predicate(s.depth))
+            .map(|s| s.sections)
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assignment_ending_on_the_last_section() {
+        let coverage =
+            Coverage::from_assignments([Assignment::new(1, usize::MAX), Assignment::new(2, 3)]);
+
+        assert_eq!(
+            coverage.segments(),
+            [
+                CoverageSegment {
+                    sections: Assignment::new(1, 1),
+                    depth: 1,
+                },
+                CoverageSegment {
+                    sections: Assignment::new(2, 3),
+                    depth: 2,
+                },
+                CoverageSegment {
+                    sections: Assignment::new(4, usize::MAX),
+                    depth: 1,
+                },
+            ]
+        );
+        assert_eq!(coverage.span(), Some(Assignment::new(1, usize::MAX)));
+        assert_eq!(coverage.max_depth().0, 2);
+    }
+}
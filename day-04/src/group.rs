@@ -0,0 +1,74 @@
+use crate::interval::{Assignment, ParseAssignmentError};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PairRelation {
+    Disjoint,
+    Overlapping,
+    // One assignment fully contains the other, which also counts as overlapping
+    Containing,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AssignmentPair {
+    pub first: usize,
+    pub second: usize,
+    pub relation: PairRelation,
+}
+
+// The assignments of every elf listed on one line of the input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssignmentGroup {
+    pub assignments: Vec<Assignment>,
+}
+
+impl AssignmentGroup {
+    pub fn from_line(line: &str) -> Result<Self, ParseAssignmentError> {
+        let assignments = line
+            .split(',')
+            .map(|a| a.parse::<Assignment>())
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(AssignmentGroup { assignments });
+    }
+
+    // Every unordered pair of assignments in the group, in line order
+    pub fn pairs(&self) -> impl Iterator<Item = AssignmentPair> + '_ {
+        let n = self.assignments.len();
+        return (0..n).flat_map(move |first| {
+            return ((first + 1)..n).map(move |second| {
+                let a = self.assignments[first];
+                let b = self.assignments[second];
+                let relation = if a.contains_assignment(b) || b.contains_assignment(a) {
+                    PairRelation::Containing
+                } else if a.overlaps_assignment(b) {
+                    PairRelation::Overlapping
+                } else {
+                    PairRelation::Disjoint
+                };
+
+                return AssignmentPair {
+                    first,
+                    second,
+                    relation,
+                };
+            });
+        });
+    }
+
+    pub fn containments(&self) -> usize {
+        return self
+            .pairs()
+            .filter(|p| p.relation == PairRelation::Containing)
+            .count();
+    }
+
+    pub fn overlaps(&self) -> usize {
+        return self
+            .pairs()
+            .filter(|p| p.relation != PairRelation::Disjoint)
+            .count();
+    }
+
+    pub fn any_overlap(&self) -> bool {
+        return self.pairs().any(|p| p.relation != PairRelation::Disjoint);
+    }
+}
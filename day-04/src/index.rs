@@ -0,0 +1,206 @@
+use std::io::BufRead;
+
+use crate::group::AssignmentGroup;
+use crate::interval::Assignment;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ElfAssignment {
+    pub line_num: usize,
+    // Position of the elf within its line, starting from 1
+    pub elf: usize,
+    pub assignment: Assignment,
+}
+
+// Static interval index: assignments are sorted by their first section, and a max-heap shaped
+// tree holds the furthest section_end below each node. A query only has to look at assignments
+// starting at or before its end, and can skip any subtree that ends before its start
+pub struct AssignmentIndex {
+    entries: Vec<ElfAssignment>,
+    max_ends: Vec<usize>,
+    leaves: usize,
+}
+
+impl AssignmentIndex {
+    pub fn new(mut entries: Vec<ElfAssignment>) -> Self {
+        entries.sort_unstable_by_key(|e| (e.assignment.section_start, e.line_num, e.elf));
+
+        let leaves = entries.len().next_power_of_two();
+        let mut max_ends = vec![0; leaves * 2];
+        for (i, entry) in entries.iter().enumerate() {
+            max_ends[leaves + i] = entry.assignment.section_end;
+        }
+        for node in (1..leaves).rev() {
+            max_ends[node] = max_ends[node * 2].max(max_ends[node * 2 + 1]);
+        }
+
+        return AssignmentIndex {
+            entries,
+            max_ends,
+            leaves,
+        };
+    }
+
+    pub fn from_reader(reader: impl BufRead) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut entries = Vec::new();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line_num = i + 1;
+            let group = AssignmentGroup::from_line(line?.trim())
+                .map_err(|e| format!("Line {line_num}: {e}"))?;
+
+            for (elf, assignment) in group.assignments.into_iter().enumerate() {
+                entries.push(ElfAssignment {
+                    line_num,
+                    elf: elf + 1,
+                    assignment,
+                });
+            }
+        }
+        return Ok(AssignmentIndex::new(entries));
+    }
+
+    pub fn len(&self) -> usize {
+        return self.entries.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.entries.is_empty();
+    }
+
+    pub fn covering_section(&self, section: usize) -> Vec<&ElfAssignment> {
+        return self.overlapping(Assignment::new(section, section));
+    }
+
+    // Assignments sharing at least one section with the range, ordered by their first section
+    pub fn overlapping(&self, range: Assignment) -> Vec<&ElfAssignment> {
+        let mut found = Vec::new();
+        if range.is_empty() {
+            return found;
+        }
+
+        let candidates = self
+            .entries
+            .partition_point(|e| e.assignment.section_start <= range.section_end);
+        self.collect(
+            1,
+            0,
+            self.leaves,
+            candidates,
+            range.section_start,
+            &mut found,
+        );
+        return found;
+    }
+
+    fn collect<'a>(
+        &'a self,
+        node: usize,
+        node_start: usize,
+        node_end: usize,
+        candidates: usize,
+        section_start: usize,
+        found: &mut Vec<&'a ElfAssignment>,
+    ) {
+        if node_start >= candidates || self.max_ends[node] < section_start {
+            return;
+        }
+
+        if node >= self.leaves {
+            found.push(&self.entries[node_start]);
+            return;
+        }
+
+        let middle = (node_start + node_end) / 2;
+        self.collect(
+            node * 2,
+            node_start,
+            middle,
+            candidates,
+            section_start,
+            found,
+        );
+        self.collect(
+            node * 2 + 1,
+            middle,
+            node_end,
+            candidates,
+            section_start,
+            found,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Seven entries so the tree has a padding leaf, with nested, touching and repeated ranges
+    const SECTIONS: [(usize, usize); 7] =
+        [(3, 7), (1, 2), (5, 5), (8, 12), (2, 9), (10, 10), (5, 5)];
+
+    fn entries(count: usize) -> Vec<ElfAssignment> {
+        return SECTIONS[..count]
+            .iter()
+            .enumerate()
+            .map(|(i, (start, end))| ElfAssignment {
+                line_num: i + 1,
+                elf: 1,
+                assignment: Assignment::new(*start, *end),
+            })
+            .collect();
+    }
+
+    fn linear_scan(entries: &[ElfAssignment], range: Assignment) -> Vec<ElfAssignment> {
+        let mut found: Vec<ElfAssignment> = entries
+            .iter()
+            .filter(|e| e.assignment.overlaps_assignment(range))
+            .copied()
+            .collect();
+        found.sort_unstable_by_key(|e| (e.assignment.section_start, e.line_num, e.elf));
+        return found;
+    }
+
+    #[test]
+    fn overlapping_matches_linear_scan() {
+        for count in 0..=SECTIONS.len() {
+            let index = AssignmentIndex::new(entries(count));
+            assert_eq!(index.len(), count);
+
+            for start in 0..=13 {
+                for end in start..=13 {
+                    let range = Assignment::new(start, end);
+                    let found: Vec<ElfAssignment> =
+                        index.overlapping(range).into_iter().copied().collect();
+                    assert_eq!(
+                        found,
+                        linear_scan(&entries(count), range),
+                        "{count} entries, range {range}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn covering_section_matches_linear_scan() {
+        for count in 0..=SECTIONS.len() {
+            let index = AssignmentIndex::new(entries(count));
+
+            for section in 0..=13 {
+                let found: Vec<ElfAssignment> = index
+                    .covering_section(section)
+                    .into_iter()
+                    .copied()
+                    .collect();
+                let expected = linear_scan(&entries(count), Assignment::new(section, section));
+                assert_eq!(found, expected, "{count} entries, section {section}");
+            }
+        }
+    }
+
+    #[test]
+    fn empty_range_overlaps_nothing() {
+        let index = AssignmentIndex::new(entries(SECTIONS.len()));
+        assert!(index.overlapping(Assignment::new(6, 5)).is_empty());
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+// Closed range of sections, from section_start to section_end inclusive
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Assignment {
+    pub section_start: usize,
+    pub section_end: usize,
+}
+
+impl Assignment {
+    pub fn new(section_start: usize, section_end: usize) -> Self {
+        return Assignment {
+            section_start,
+            section_end,
+        };
+    }
+
+    // Saturates for the one range too long to count, 0 up to usize::MAX
+    pub fn len(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        return (self.section_end - self.section_start).saturating_add(1);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.section_start > self.section_end;
+    }
+
+    pub fn contains_section(&self, section: usize) -> bool {
+        return section >= self.section_start && section <= self.section_end;
+    }
+
+    pub fn contains_assignment(&self, assignment: Assignment) -> bool {
+        return self.intersection(assignment) == Some(assignment);
+    }
+
+    pub fn overlaps_assignment(&self, assignment: Assignment) -> bool {
+        return self.intersection(assignment).is_some();
+    }
+
+    pub fn intersection(&self, assignment: Assignment) -> Option<Assignment> {
+        let intersection = Assignment::new(
+            self.section_start.max(assignment.section_start),
+            self.section_end.min(assignment.section_end),
+        );
+
+        if intersection.is_empty() {
+            return None;
+        }
+        return Some(intersection);
+    }
+
+    // Only defined when the two assignments overlap or are directly next to each other, as the
+    // union would otherwise have a gap in it
+    pub fn union(&self, assignment: Assignment) -> Option<Assignment> {
+        if self.is_empty() {
+            return Some(assignment);
+        } else if assignment.is_empty() {
+            return Some(*self);
+        }
+
+        if self.section_start > assignment.section_end.saturating_add(1)
+            || assignment.section_start > self.section_end.saturating_add(1)
+        {
+            return None;
+        }
+
+        return Some(Assignment::new(
+            self.section_start.min(assignment.section_start),
+            self.section_end.max(assignment.section_end),
+        ));
+    }
+
+    // The sections of this assignment not covered by the other, which may be split in two
+    pub fn difference(&self, assignment: Assignment) -> Vec<Assignment> {
+        let intersection = match self.intersection(assignment) {
+            Some(i) => i,
+            None if self.is_empty() => return Vec::new(),
+            None => return vec![*self],
+        };
+
+        let mut pieces = Vec::with_capacity(2);
+        if intersection.section_start > self.section_start {
+            pieces.push(Assignment::new(
+                self.section_start,
+                intersection.section_start - 1,
+            ));
+        }
+        if intersection.section_end < self.section_end {
+            pieces.push(Assignment::new(
+                intersection.section_end + 1,
+                self.section_end,
+            ));
+        }
+        return pieces;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseAssignmentError {
+    MissingSeparator(String),
+    InvalidSection(String, ParseIntError),
+    StartAfterEnd(Assignment),
+}
+
+impl fmt::Display for ParseAssignmentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            ParseAssignmentError::MissingSeparator(s) => {
+                write!(f, "Assignment {s:?} must be two sections separated by '-'")
+            }
+            ParseAssignmentError::InvalidSection(s, e) => {
+                write!(f, "Section {s:?} is not a valid number: {e}")
+            }
+            ParseAssignmentError::StartAfterEnd(a) => write!(
+                f,
+                "Assignment {a} starts after it ends, section start must not exceed section end"
+            ),
+        };
+    }
+}
+
+impl std::error::Error for ParseAssignmentError {}
+
+impl FromStr for Assignment {
+    type Err = ParseAssignmentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| ParseAssignmentError::MissingSeparator(s.to_string()))?;
+        let parse_section = |section: &str| {
+            return section
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| ParseAssignmentError::InvalidSection(section.to_string(), e));
+        };
+        let assignment = Assignment::new(parse_section(start)?, parse_section(end)?);
+
+        if assignment.is_empty() {
+            return Err(ParseAssignmentError::StartAfterEnd(assignment));
+        }
+        return Ok(assignment);
+    }
+}
+
+// Formatted the same way as in the puzzle input
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}-{}", self.section_start, self.section_end);
+    }
+}
+
+// Sorted list of disjoint assignments, where overlapping or touching ranges are merged together
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    intervals: Vec<Assignment>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        return IntervalSet::default();
+    }
+
+    pub fn insert(&mut self, assignment: Assignment) {
+        if assignment.is_empty() {
+            return;
+        }
+
+        let start = self
+            .intervals
+            .partition_point(|i| i.section_end.saturating_add(1) < assignment.section_start);
+        let mut end = start;
+        let mut merged = assignment;
+
+        while end < self.intervals.len() {
+            match merged.union(self.intervals[end]) {
+                Some(union) => merged = union,
+                None => break,
+            }
+            end += 1;
+        }
+
+        self.intervals.splice(start..end, [merged]);
+    }
+
+    pub fn remove(&mut self, assignment: Assignment) {
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|i| i.difference(assignment))
+            .collect();
+    }
+
+    pub fn contains_section(&self, section: usize) -> bool {
+        let i = self.intervals.partition_point(|i| i.section_end < section);
+        return self
+            .intervals
+            .get(i)
+            .is_some_and(|i| i.contains_section(section));
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intervals = Vec::new();
+        let (mut a, mut b) = (0, 0);
+
+        while a < self.intervals.len() && b < other.intervals.len() {
+            if let Some(i) = self.intervals[a].intersection(other.intervals[b]) {
+                intervals.push(i);
+            }
+            if self.intervals[a].section_end < other.intervals[b].section_end {
+                a += 1;
+            } else {
+                b += 1;
+            }
+        }
+        return IntervalSet { intervals };
+    }
+
+    // Total number of sections covered
+    pub fn len(&self) -> usize {
+        return self
+            .intervals
+            .iter()
+            .fold(0usize, |total, i| total.saturating_add(i.len()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.intervals.is_empty();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Assignment> {
+        return self.intervals.iter();
+    }
+}
+
+impl FromIterator<Assignment> for IntervalSet {
+    fn from_iter<T: IntoIterator<Item = Assignment>>(iter: T) -> Self {
+        let mut set = IntervalSet::new();
+        for assignment in iter {
+            set.insert(assignment);
+        }
+        return set;
+    }
+}
+
+impl fmt::Display for IntervalSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, assignment) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{assignment}")?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn len_counts_sections() {
+        assert_eq!(Assignment::new(2, 4).len(), 3);
+        assert_eq!(Assignment::new(5, 5).len(), 1);
+        assert_eq!(Assignment::new(6, 5).len(), 0);
+    }
+
+    #[test]
+    fn len_does_not_overflow_at_the_last_section() {
+        assert_eq!(Assignment::new(1, usize::MAX).len(), usize::MAX);
+        assert_eq!(Assignment::new(usize::MAX, usize::MAX).len(), 1);
+        assert_eq!(Assignment::new(0, usize::MAX).len(), usize::MAX);
+    }
+}
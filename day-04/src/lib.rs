@@ -0,0 +1,5 @@
+pub mod coverage;
+pub mod group;
+pub mod index;
+pub mod interval;
+pub mod planner;
@@ -1,90 +1,263 @@
+use day_04::coverage::Coverage;
+use day_04::group::AssignmentGroup;
+use day_04::index::AssignmentIndex;
+use day_04::interval::{Assignment, IntervalSet};
+use day_04::planner;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::num::ParseIntError;
+use std::path::Path;
 
-#[derive(Clone, Copy)]
-struct Assignment {
-    section_start: usize,
-    section_end: usize,
+fn read_group(line: &str, line_num: usize) -> Result<AssignmentGroup, String> {
+    return AssignmentGroup::from_line(line.trim()).map_err(|e| format!("Line {line_num}: {e}"));
 }
 
-impl Assignment {
-    fn new(section_start: usize, section_end: usize) -> Self {
-        return Assignment {
-            section_start,
-            section_end,
-        };
-    }
+fn solution_part_1(path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let mut reader = BufReader::new(file);
+    let mut line = String::with_capacity(8);
+    let mut line_num = 0;
+    let mut total_containments = 0;
 
-    fn from_line(line: &str) -> Result<(Assignment, Assignment), ParseIntError> {
-        let mut sections = [0; 4];
+    while reader.read_line(&mut line)? != 0 {
+        line_num += 1;
+        total_containments += read_group(&line, line_num)?.containments();
+        line.clear();
+    }
+    return Ok(total_containments);
+}
 
-        for (i, section) in line.split(&[',', '-']).enumerate() {
-            sections[i] = section.parse::<usize>()?;
-        }
+fn solution_part_2(path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let mut reader = BufReader::new(file);
+    let mut line = String::with_capacity(8);
+    let mut line_num = 0;
+    let mut total_overlaps = 0;
 
-        let elf_1_sections = Assignment::new(sections[0], sections[1]);
-        let elf_2_sections = Assignment::new(sections[2], sections[3]);
-        return Ok((elf_1_sections, elf_2_sections));
+    while reader.read_line(&mut line)? != 0 {
+        line_num += 1;
+        total_overlaps += read_group(&line, line_num)?.overlaps();
+        line.clear();
     }
+    return Ok(total_overlaps);
+}
 
-    fn contains_section(&self, section: usize) -> bool {
-        return section >= self.section_start && section <= self.section_end;
+fn count_overlapping_groups(path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let mut reader = BufReader::new(file);
+    let mut line = String::with_capacity(8);
+    let mut line_num = 0;
+    let mut overlapping_groups = 0;
+
+    while reader.read_line(&mut line)? != 0 {
+        line_num += 1;
+        if read_group(&line, line_num)?.any_overlap() {
+            overlapping_groups += 1;
+        }
+        line.clear();
     }
+    return Ok(overlapping_groups);
+}
+
+fn pairs_report(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let mut reader = BufReader::new(file);
+    let mut line = String::with_capacity(8);
+    let mut line_num = 0;
 
-    fn contains_assignment(&self, assignment: Assignment) -> bool {
-        return self.section_start <= assignment.section_start
-            && self.section_end >= assignment.section_end;
+    while reader.read_line(&mut line)? != 0 {
+        line_num += 1;
+        let group = read_group(&line, line_num)?;
+
+        println!("Line {line_num}:");
+        for pair in group.pairs() {
+            println!(
+                "  Elf {} ({}) and elf {} ({}): {:?}",
+                pair.first + 1,
+                group.assignments[pair.first],
+                pair.second + 1,
+                group.assignments[pair.second],
+                pair.relation
+            );
+        }
+        line.clear();
     }
+    return Ok(());
+}
 
-    fn overlaps_assignment(&self, assignment: Assignment) -> bool {
-        return self.contains_assignment(assignment)
-            || assignment.contains_section(self.section_start)
-            || assignment.contains_section(self.section_end);
+fn describe_sections(sections: &IntervalSet) -> String {
+    if sections.is_empty() {
+        return String::from("none");
     }
+    return format!("{} ({} sections)", sections, sections.len());
 }
 
-fn solution_part_1() -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
+fn coverage_report(path: &Path, threshold: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
     let mut reader = BufReader::new(file);
     let mut line = String::with_capacity(8);
-    let mut total_containments = 0;
+    let mut line_num = 0;
+    let mut assignments = Vec::new();
 
     while reader.read_line(&mut line)? != 0 {
-        let (assignment_1, assignment_2) = Assignment::from_line(line.trim())?;
-
-        if assignment_1.contains_assignment(assignment_2)
-            || assignment_2.contains_assignment(assignment_1)
-        {
-            total_containments += 1;
-        }
+        line_num += 1;
+        assignments.extend(read_group(&line, line_num)?.assignments);
         line.clear();
     }
-    return Ok(total_containments);
+
+    let coverage = Coverage::from_assignments(assignments);
+    let span = coverage.span().ok_or("No assignments found")?;
+    let uncovered = coverage.uncovered();
+    let crowded = coverage.covered_more_than(threshold);
+    let (max_depth, deepest) = coverage.max_depth();
+
+    println!("Sections {span}");
+    println!("Uncovered sections: {}", describe_sections(&uncovered));
+    println!(
+        "Sections covered by more than {threshold} elves: {}",
+        describe_sections(&crowded)
+    );
+    println!(
+        "Maximum coverage of {max_depth} elves at sections: {}",
+        describe_sections(&deepest)
+    );
+    println!("Coverage histogram:");
+
+    let histogram = coverage.histogram();
+    let most_sections = histogram.values().copied().max().unwrap_or(1);
+    for (depth, sections) in histogram.iter() {
+        // Widened so that counts near usize::MAX don't overflow when scaled
+        let bar_len = (*sections as u128 * 50).div_ceil(most_sections as u128);
+        let bar = "#".repeat(bar_len as usize);
+        println!("{depth:>4} elves {sections:>6} sections {bar}");
+    }
+    return Ok(());
 }
 
-fn solution_part_2() -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
+fn reassignment_plan(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
     let mut reader = BufReader::new(file);
     let mut line = String::with_capacity(8);
-    let mut total_overlaps = 0;
+    let mut line_num = 0;
+    let mut total_edit_distance = 0;
 
     while reader.read_line(&mut line)? != 0 {
-        let (assignment_1, assignment_2) = Assignment::from_line(line.trim())?;
+        line_num += 1;
+        let group = read_group(&line, line_num)?;
+        line.clear();
 
-        if assignment_1.overlaps_assignment(assignment_2) {
-            total_overlaps += 1;
+        if !group.any_overlap() {
+            continue;
         }
-        line.clear();
+
+        let plan = planner::plan_reassignment(&group.assignments);
+        let original: Vec<String> = group.assignments.iter().map(|a| a.to_string()).collect();
+        let planned: Vec<String> = plan.assignments.iter().map(|a| a.to_string()).collect();
+        let note = if plan.exact { "" } else { " (approximate)" };
+
+        total_edit_distance += plan.edit_distance;
+        println!(
+            "Line {line_num}: {} -> {}, moved {} sections{note}",
+            original.join(","),
+            planned.join(","),
+            plan.edit_distance
+        );
     }
-    return Ok(total_overlaps);
+
+    println!("Total sections moved: {total_edit_distance}");
+    return Ok(());
+}
+
+// Reads queries from stdin, each either a single section such as "42" or a range such as "10-20"
+fn query_index(path: &Path, limit: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let index = AssignmentIndex::from_reader(BufReader::new(file))?;
+    println!("Indexed {} assignments", index.len());
+
+    for query in std::io::stdin().lock().lines() {
+        let query = query?;
+        let query = query.trim();
+        if query.is_empty() {
+            continue;
+        }
+
+        let found = if query.contains('-') {
+            match query.parse::<Assignment>() {
+                Ok(range) => index.overlapping(range),
+                Err(e) => {
+                    println!("{e}");
+                    continue;
+                }
+            }
+        } else {
+            match query.parse::<usize>() {
+                Ok(section) => index.covering_section(section),
+                Err(e) => {
+                    println!("Section {query:?} is not a valid number: {e}");
+                    continue;
+                }
+            }
+        };
+
+        println!("{query}: {} assignments", found.len());
+        for entry in found.iter().take(limit) {
+            println!(
+                "  Line {} elf {}: {}",
+                entry.line_num, entry.elf, entry.assignment
+            );
+        }
+        if found.len() > limit {
+            println!("  ... {} more", found.len() - limit);
+        }
+    }
+    return Ok(());
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let part_1 = solution_part_1()?;
-    let part_2 = solution_part_2()?;
+    let mut args = std::env::args().skip(1);
+    let mut input = String::from("input.txt");
+    let mut coverage = false;
+    let mut pairs = false;
+    let mut query = false;
+    let mut plan = false;
+    let mut limit = 20;
+    let mut threshold = 1;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--coverage" => coverage = true,
+            "--pairs" => pairs = true,
+            "--query" => query = true,
+            "--plan" => plan = true,
+            "--limit" => limit = args.next().ok_or("--limit requires a value")?.parse()?,
+            "--threshold" => {
+                threshold = args.next().ok_or("--threshold requires a value")?.parse()?;
+            }
+            _ => input = arg,
+        }
+    }
+
+    if coverage {
+        return coverage_report(Path::new(&input), threshold);
+    }
+
+    if query {
+        return query_index(Path::new(&input), limit);
+    }
+
+    if plan {
+        return reassignment_plan(Path::new(&input));
+    }
+
+    if pairs {
+        return pairs_report(Path::new(&input));
+    }
+
+    let part_1 = solution_part_1(Path::new(&input))?;
+    let part_2 = solution_part_2(Path::new(&input))?;
+    let overlapping_groups = count_overlapping_groups(Path::new(&input))?;
 
     println!("Part 1 - Fully contained assignments: {part_1}");
     println!("Part 2 - Overlapping assignments: {part_2}");
+    println!("Groups with any overlap: {overlapping_groups}");
     return Ok(());
 }
@@ -0,0 +1,245 @@
+use crate::interval::Assignment;
+
+// Trying every left-to-right order of the assignments is exact but grows factorially, so larger
+// groups keep the order of their midpoints instead
+const MAX_EXACT_GROUP_SIZE: usize = 8;
+
+// Sections are numbered from 1
+const FIRST_SECTION: i64 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reassignment {
+    // Proposed assignments, in the same order as the original ones
+    pub assignments: Vec<Assignment>,
+    // Total number of sections every start and end had to move by
+    pub edit_distance: usize,
+    // False when the group was too large to try every order of assignments
+    pub exact: bool,
+}
+
+// L1 isotonic regression using the pool adjacent violators algorithm: finds the non-decreasing
+// sequence closest to the targets, where each pooled block takes the median of its targets
+fn isotonic_median(targets: &[i64]) -> Vec<i64> {
+    let mut blocks: Vec<Vec<i64>> = Vec::new();
+    let median = |block: &Vec<i64>| block[(block.len() - 1) / 2];
+
+    for target in targets {
+        blocks.push(vec![*target]);
+
+        while blocks.len() >= 2
+            && median(&blocks[blocks.len() - 2]) > median(&blocks[blocks.len() - 1])
+        {
+            let last = blocks.pop().unwrap_or_default();
+            let previous = blocks
+                .last_mut()
+                .expect("Checked there are at least two blocks");
+            previous.extend(last);
+            previous.sort_unstable();
+        }
+    }
+
+    return blocks
+        .iter()
+        .flat_map(|b| std::iter::repeat_n(median(b), b.len()))
+        .collect();
+}
+
+// Cheapest way to lay the assignments out left to right in the given order without overlapping
+fn plan_order(assignments: &[Assignment], order: &[usize]) -> (Vec<Assignment>, usize) {
+    // Each end must be at least the start of its assignment, and each start must be at least one
+    // past the previous end. Subtracting the required gaps turns this into a non-decreasing fit
+    let mut offsets = Vec::with_capacity(order.len() * 2);
+    let mut targets = Vec::with_capacity(order.len() * 2);
+    let mut offset = 0;
+
+    for (i, a) in order.iter().map(|i| assignments[*i]).enumerate() {
+        if i > 0 {
+            offset += 1;
+        }
+        offsets.push(offset);
+        targets.push(a.section_start as i64 - offset);
+        offsets.push(offset);
+        targets.push(a.section_end as i64 - offset);
+    }
+
+    let fitted = isotonic_median(&targets);
+    let mut planned = assignments.to_vec();
+    let mut edit_distance = 0;
+
+    for (k, i) in order.iter().enumerate() {
+        // Sections start from 1, and clamping a non-decreasing fit to a lower bound keeps it optimal
+        // under that bound. Offsets are never negative, so every section stays at least 1
+        let start = fitted[k * 2].max(FIRST_SECTION) + offsets[k * 2];
+        let end = fitted[k * 2 + 1].max(FIRST_SECTION) + offsets[k * 2 + 1];
+        planned[*i] = Assignment::new(start as usize, end as usize);
+        edit_distance += planned[*i]
+            .section_start
+            .abs_diff(assignments[*i].section_start);
+        edit_distance += planned[*i]
+            .section_end
+            .abs_diff(assignments[*i].section_end);
+    }
+    return (planned, edit_distance);
+}
+
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut result = Vec::new();
+    for perm in permutations(n - 1) {
+        for i in 0..n {
+            let mut p = perm.clone();
+            p.insert(i, n - 1);
+            result.push(p);
+        }
+    }
+    return result;
+}
+
+// Smallest change to the assignments, counted in sections moved, that leaves no two of them
+// overlapping while keeping each one at least a section long
+pub fn plan_reassignment(assignments: &[Assignment]) -> Reassignment {
+    let exact = assignments.len() <= MAX_EXACT_GROUP_SIZE;
+    let orders = if exact {
+        permutations(assignments.len())
+    } else {
+        let mut order: Vec<usize> = (0..assignments.len()).collect();
+        order.sort_by_key(|i| assignments[*i].section_start + assignments[*i].section_end);
+        vec![order]
+    };
+
+    let mut best: Option<(Vec<Assignment>, usize)> = None;
+    for order in orders.iter() {
+        let (planned, edit_distance) = plan_order(assignments, order);
+        if best.as_ref().is_none_or(|b| edit_distance < b.1) {
+            best = Some((planned, edit_distance));
+        }
+    }
+
+    let (assignments, edit_distance) = best.unwrap_or_default();
+    return Reassignment {
+        assignments,
+        edit_distance,
+        exact,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(original: &[Assignment], planned: &[Assignment]) -> usize {
+        return original
+            .iter()
+            .zip(planned)
+            .map(|(a, b)| {
+                a.section_start.abs_diff(b.section_start) + a.section_end.abs_diff(b.section_end)
+            })
+            .sum();
+    }
+
+    fn any_overlap(assignments: &[Assignment]) -> bool {
+        return assignments.iter().enumerate().any(|(i, a)| {
+            assignments[i + 1..]
+                .iter()
+                .any(|b| a.overlaps_assignment(*b))
+        });
+    }
+
+    // Tries every layout of non-empty assignments within the sections that could matter, which
+    // is never further right than one past the last end for each assignment
+    fn brute_force(assignments: &[Assignment]) -> usize {
+        let last = assignments.iter().map(|a| a.section_end).max().unwrap_or(0) + assignments.len();
+        let options: Vec<Assignment> = (1..=last)
+            .flat_map(|start| (start..=last).map(move |end| Assignment::new(start, end)))
+            .collect();
+
+        let mut best = usize::MAX;
+        let mut layout = vec![0; assignments.len()];
+        loop {
+            let planned: Vec<Assignment> = layout.iter().map(|i| options[*i]).collect();
+            if !any_overlap(&planned) {
+                best = best.min(distance(assignments, &planned));
+            }
+
+            let mut position = 0;
+            while position < layout.len() && layout[position] + 1 == options.len() {
+                layout[position] = 0;
+                position += 1;
+            }
+            if position == layout.len() {
+                return best;
+            }
+            layout[position] += 1;
+        }
+    }
+
+    fn check_plan(sections: &[(usize, usize)]) {
+        let assignments: Vec<Assignment> = sections
+            .iter()
+            .map(|(start, end)| Assignment::new(*start, *end))
+            .collect();
+        let plan = plan_reassignment(&assignments);
+
+        assert!(plan.exact);
+        assert_eq!(plan.assignments.len(), assignments.len());
+        assert!(
+            plan.assignments
+                .iter()
+                .all(|a| !a.is_empty() && a.section_start >= 1),
+            "{sections:?} planned as {:?}",
+            plan.assignments
+        );
+        assert!(!any_overlap(&plan.assignments), "{:?}", plan.assignments);
+        assert_eq!(
+            plan.edit_distance,
+            distance(&assignments, &plan.assignments)
+        );
+        assert_eq!(
+            plan.edit_distance,
+            brute_force(&assignments),
+            "{sections:?} planned as {:?}",
+            plan.assignments
+        );
+    }
+
+    #[test]
+    fn pairs_are_minimal() {
+        check_plan(&[(2, 4), (6, 8)]);
+        check_plan(&[(2, 4), (4, 6)]);
+        check_plan(&[(2, 6), (4, 8)]);
+        check_plan(&[(5, 7), (1, 6)]);
+    }
+
+    #[test]
+    fn containment_is_minimal() {
+        check_plan(&[(2, 8), (3, 4)]);
+        check_plan(&[(4, 4), (1, 9)]);
+    }
+
+    #[test]
+    fn identical_ranges_are_minimal() {
+        check_plan(&[(1, 1), (1, 1)]);
+        check_plan(&[(2, 4), (2, 4)]);
+        check_plan(&[(1, 3), (1, 3)]);
+    }
+
+    #[test]
+    fn three_way_groups_are_minimal() {
+        check_plan(&[(1, 5), (2, 6), (3, 7)]);
+        check_plan(&[(1, 1), (1, 1), (1, 1)]);
+        check_plan(&[(2, 6), (3, 3), (5, 8)]);
+        check_plan(&[(1, 2), (4, 5), (2, 4)]);
+    }
+
+    #[test]
+    fn sections_start_from_one() {
+        let plan = plan_reassignment(&[Assignment::new(1, 1), Assignment::new(1, 1)]);
+        let mut planned = plan.assignments.clone();
+        planned.sort_unstable();
+        assert_eq!(planned, vec![Assignment::new(1, 1), Assignment::new(2, 2)]);
+        assert_eq!(plan.edit_distance, 2);
+    }
+}
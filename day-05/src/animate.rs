@@ -0,0 +1,71 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use crate::crane::Crane;
+use crate::{CrateMovement, CrateStacks};
+
+// ANSI escape codes to clear the terminal and move the cursor back to the top left
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+fn draw_frame(crate_stacks: &CrateStacks, caption: &str) -> Result<(), std::io::Error> {
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "{CLEAR_SCREEN}{crate_stacks}\n{caption}\n")?;
+    return stdout.flush();
+}
+
+// Redraws the stacks after every `every` moves, and once more after the final move
+pub fn animate(
+    path: &Path,
+    crane: &dyn Crane,
+    every: usize,
+    delay_ms: u64,
+    empty_placeholder: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if every == 0 {
+        return Err("Animation must redraw after at least 1 move")?;
+    }
+
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let mut reader = BufReader::new(file);
+    let mut line = String::with_capacity(16);
+    let mut crate_stacks = CrateStacks::from_file(&mut reader)?;
+    let mut num_moves = 0;
+    let mut last_drawn = None;
+
+    draw_frame(&crate_stacks, "Start")?;
+
+    while reader.read_line(&mut line)? != 0 {
+        let l = line.trim();
+
+        if !l.is_empty() {
+            let crate_movement = CrateMovement::from_string(l)?;
+            crane
+                .move_crates(&mut crate_stacks, crate_movement)
+                .map_err(|e| format!("Move {} ({crate_movement}): {e}", num_moves + 1))?;
+            num_moves += 1;
+
+            if num_moves % every == 0 {
+                thread::sleep(Duration::from_millis(delay_ms));
+                draw_frame(
+                    &crate_stacks,
+                    &format!("Move {num_moves}: {crate_movement}"),
+                )?;
+                last_drawn = Some(num_moves);
+            }
+        }
+        line.clear();
+    }
+
+    if last_drawn != Some(num_moves) {
+        thread::sleep(Duration::from_millis(delay_ms));
+        draw_frame(&crate_stacks, &format!("Move {num_moves}"))?;
+    }
+    println!(
+        "Top crates: {}",
+        crate_stacks.get_top_crates(empty_placeholder)
+    );
+    return Ok(());
+}
@@ -0,0 +1,127 @@
+use std::time::{Duration, Instant};
+
+use crate::crane::{Crane, CrateMover9000, CrateMover9001};
+use crate::{CrateId, CrateMovement, CrateStacks};
+
+const NUM_STACKS: usize = 9;
+const STACK_HEIGHT: usize = 20_000;
+const MAX_CRATES_MOVED: usize = 5_000;
+
+// The previous way of moving crates, copying them onto the destination one at a time and then
+// shortening the source stack
+fn move_crates_one_by_one(stacks: &mut [Vec<CrateId>], movement: CrateMovement, reverse: bool) {
+    let start_index = stacks[movement.from_stack].len() - movement.num_crates;
+    for i in 0..movement.num_crates {
+        let offset = if reverse {
+            movement.num_crates - 1 - i
+        } else {
+            i
+        };
+        let c = stacks[movement.from_stack][start_index + offset];
+        stacks[movement.to_stack].push(c);
+    }
+
+    stacks[movement.from_stack].truncate(start_index);
+}
+
+// Every stack starts out full, with the crates labelled A to Z over and over
+fn tall_stacks() -> CrateStacks {
+    return CrateStacks {
+        stacks: (0..NUM_STACKS)
+            .map(|s| {
+                let ids = s * STACK_HEIGHT..(s + 1) * STACK_HEIGHT;
+                ids.map(|i| (i % 26) as CrateId).collect()
+            })
+            .collect(),
+        names: (b'A'..=b'Z').map(|c| (c as char).to_string()).collect(),
+        history: Vec::new(),
+        drawing_lines: 0,
+    };
+}
+
+// Pseudo-random movements between different stacks, each taking at least one crate and never
+// more than the source stack holds at that point
+fn random_movements(num_movements: usize) -> Vec<CrateMovement> {
+    let mut heights = [STACK_HEIGHT; NUM_STACKS];
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut random = |bound: usize| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        return (state % bound as u64) as usize;
+    };
+    let mut movements = Vec::with_capacity(num_movements);
+
+    for _ in 0..num_movements {
+        let from_stack = loop {
+            let stack = random(NUM_STACKS);
+            if heights[stack] > 0 {
+                break stack;
+            }
+        };
+        let to_stack = (from_stack + 1 + random(NUM_STACKS - 1)) % NUM_STACKS;
+        let num_crates = 1 + random(heights[from_stack].min(MAX_CRATES_MOVED));
+
+        heights[from_stack] -= num_crates;
+        heights[to_stack] += num_crates;
+        movements.push(CrateMovement {
+            num_crates,
+            from_stack,
+            to_stack,
+        });
+    }
+    return movements;
+}
+
+fn time_movements(move_crates: impl FnMut(CrateMovement), movements: &[CrateMovement]) -> Duration {
+    let start = Instant::now();
+    movements.iter().copied().for_each(move_crates);
+    return start.elapsed();
+}
+
+pub fn compare_movers(num_movements: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let movements = random_movements(num_movements);
+    println!(
+        "Carrying out {num_movements} movements of up to {MAX_CRATES_MOVED} crates between \
+         {NUM_STACKS} stacks of {STACK_HEIGHT} crates"
+    );
+    println!("Crane            One by one (ms)  Bulk (ms)  Speed-up");
+
+    let cranes: [(&dyn Crane, bool); 2] = [(&CrateMover9000, true), (&CrateMover9001, false)];
+    for (crane, reverse) in cranes {
+        let mut one_by_one = tall_stacks().stacks;
+        let one_by_one_time = time_movements(
+            |movement| move_crates_one_by_one(&mut one_by_one, movement, reverse),
+            &movements,
+        );
+
+        let mut bulk = tall_stacks();
+        let mut error = None;
+        let bulk_time = time_movements(
+            |movement| {
+                if let Err(e) = crane.move_crates(&mut bulk, movement) {
+                    error.get_or_insert(format!("{movement}: {e}"));
+                }
+            },
+            &movements,
+        );
+
+        if let Some(error) = error {
+            return Err(error)?;
+        }
+        if one_by_one != bulk.stacks {
+            return Err(format!(
+                "Stacks differ after moving with the {}",
+                crane.name()
+            ))?;
+        }
+        println!(
+            "{:<15}  {:>15.1}  {:>9.1}  {:>7.1}x",
+            crane.name(),
+            one_by_one_time.as_secs_f64() * 1000.0,
+            bulk_time.as_secs_f64() * 1000.0,
+            one_by_one_time.as_secs_f64() / bulk_time.as_secs_f64()
+        );
+    }
+    return Ok(());
+}
@@ -0,0 +1,120 @@
+use crate::{CrateMovement, CrateStacks, MovementError};
+
+pub trait Crane {
+    fn name(&self) -> String;
+
+    // Carries out the movement, returning how many lifts the crane needed
+    fn move_crates(
+        &self,
+        stacks: &mut CrateStacks,
+        movement: CrateMovement,
+    ) -> Result<usize, MovementError>;
+}
+
+// Moves crates one at a time, reversing their order
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        return String::from("CrateMover 9000");
+    }
+
+    fn move_crates(
+        &self,
+        stacks: &mut CrateStacks,
+        movement: CrateMovement,
+    ) -> Result<usize, MovementError> {
+        stacks.move_crates_sequential(movement)?;
+        return Ok(movement.num_crates);
+    }
+}
+
+// Moves every crate in one lift, keeping their order
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        return String::from("CrateMover 9001");
+    }
+
+    fn move_crates(
+        &self,
+        stacks: &mut CrateStacks,
+        movement: CrateMovement,
+    ) -> Result<usize, MovementError> {
+        stacks.move_crates_grouped(movement)?;
+        return Ok(movement.num_crates.min(1));
+    }
+}
+
+// Moves up to `capacity` crates per lift, keeping the order of the crates within each lift
+pub struct CapacityCrane {
+    capacity: usize,
+}
+
+impl CapacityCrane {
+    pub fn new(capacity: usize) -> Result<Self, &'static str> {
+        if capacity == 0 {
+            return Err("Crane capacity must be at least 1");
+        }
+        return Ok(CapacityCrane { capacity });
+    }
+}
+
+impl Crane for CapacityCrane {
+    fn name(&self) -> String {
+        return format!("Capacity {} crane", self.capacity);
+    }
+
+    fn move_crates(
+        &self,
+        stacks: &mut CrateStacks,
+        movement: CrateMovement,
+    ) -> Result<usize, MovementError> {
+        stacks.check_movement(movement)?;
+        let mut remaining = movement.num_crates;
+        let mut lifts = 0;
+
+        while remaining > 0 {
+            let num_crates = remaining.min(self.capacity);
+            stacks.move_crates_grouped(CrateMovement {
+                num_crates,
+                ..movement
+            })?;
+            remaining -= num_crates;
+            lifts += 1;
+        }
+        return Ok(lifts);
+    }
+}
+
+// Moves every crate in one lift, turning the group upside-down before setting it down. The
+// crates end up in the same order as with the CrateMover 9000, but in a single lift
+pub struct FlippingCrane;
+
+impl Crane for FlippingCrane {
+    fn name(&self) -> String {
+        return String::from("Flipping crane");
+    }
+
+    fn move_crates(
+        &self,
+        stacks: &mut CrateStacks,
+        movement: CrateMovement,
+    ) -> Result<usize, MovementError> {
+        stacks.move_crates_sequential(movement)?;
+        return Ok(movement.num_crates.min(1));
+    }
+}
+
+pub fn crane_from_name(name: &str) -> Result<Box<dyn Crane>, Box<dyn std::error::Error>> {
+    return match name.split_once(':') {
+        None if name == "9000" => Ok(Box::new(CrateMover9000)),
+        None if name == "9001" => Ok(Box::new(CrateMover9001)),
+        None if name == "flipping" => Ok(Box::new(FlippingCrane)),
+        Some(("capacity", capacity)) => Ok(Box::new(CapacityCrane::new(capacity.parse()?)?)),
+        _ => Err(format!(
+            "Unknown crane {name:?}, expected 9000, 9001, flipping or capacity:<crates>"
+        ))?,
+    };
+}
@@ -1,5 +1,20 @@
+mod animate;
+mod bench;
+mod crane;
+mod planner;
+mod step;
+mod validate;
+
+use crane::{Crane, CrateMover9000, CrateMover9001};
+use std::collections::HashMap;
+use std::fmt;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+// Crates are usually a single letter, but generated drawings can label them with longer IDs.
+// Stacks hold the index of each crate's label in the name table, which keeps moves cheap
+type CrateId = u32;
 
 #[derive(Clone, Copy)]
 struct CrateMovement {
@@ -12,9 +27,9 @@ impl CrateMovement {
     fn from_string(movement: &str) -> Result<CrateMovement, Box<dyn std::error::Error>> {
         let par_err = "Invalid Movement Parameter";
         let mut tokens = movement.split_whitespace().skip(1).step_by(2);
-        let num_crates = tokens.next().ok_or_else(|| par_err)?.parse::<usize>()?;
-        let from_stack = tokens.next().ok_or_else(|| par_err)?.parse::<usize>()?;
-        let to_stack = tokens.next().ok_or_else(|| par_err)?.parse::<usize>()?;
+        let num_crates = tokens.next().ok_or(par_err)?.parse::<usize>()?;
+        let from_stack = tokens.next().ok_or(par_err)?.parse::<usize>()?;
+        let to_stack = tokens.next().ok_or(par_err)?.parse::<usize>()?;
 
         if from_stack == 0 || to_stack == 0 {
             return Result::Err(std::io::Error::new(
@@ -31,127 +46,615 @@ impl CrateMovement {
     }
 }
 
+// Formatted the same way as in the puzzle input
+impl fmt::Display for CrateMovement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(
+            f,
+            "move {} from {} to {}",
+            self.num_crates,
+            self.from_stack + 1,
+            self.to_stack + 1
+        );
+    }
+}
+
+// Why a movement cannot be carried out. Stacks are numbered from 0, like in `CrateMovement`
+#[derive(Debug)]
+enum MovementError {
+    StackOutOfRange {
+        stack: usize,
+        num_stacks: usize,
+    },
+    Underflow {
+        stack: usize,
+        available: usize,
+        needed: usize,
+    },
+    MoveToSelf {
+        stack: usize,
+    },
+}
+
+impl fmt::Display for MovementError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            MovementError::StackOutOfRange { stack, num_stacks } => write!(
+                f,
+                "Out-of-range stack {}, there are only {num_stacks} stacks",
+                stack + 1
+            ),
+            MovementError::Underflow {
+                stack,
+                available,
+                needed,
+            } => write!(
+                f,
+                "Underflow, stack {} holds {available} crates but {needed} are moved",
+                stack + 1
+            ),
+            MovementError::MoveToSelf { stack } => {
+                write!(f, "Move from stack {} to itself", stack + 1)
+            }
+        };
+    }
+}
+
+impl std::error::Error for MovementError {}
+
+// A movement that has been carried out, along with the crates it took off the top of the source
+// stack (bottom to top) so that it can be undone whichever crane moved them
+struct AppliedMovement {
+    movement: CrateMovement,
+    crates: Vec<CrateId>,
+}
+
+// A row of stack numbers, returning each number with the doubled column of its centre
+fn parse_label_row(line: &str) -> Option<Vec<(usize, usize)>> {
+    let mut labels = Vec::new();
+    let mut start = None;
+    let chars: Vec<char> = line.chars().collect();
+
+    for i in 0..=chars.len() {
+        match (chars.get(i).filter(|c| !c.is_whitespace()), start) {
+            (Some(_), None) => start = Some(i),
+            (None, Some(s)) => {
+                let label: String = chars[s..i].iter().collect();
+                labels.push((label.parse::<usize>().ok()?, s + i - 1));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if labels.is_empty() {
+        return None;
+    }
+    return Some(labels);
+}
+
+// A crate in a row of the drawing, with the columns of its opening and closing brackets
+struct DrawnCrate {
+    name: String,
+    first_column: usize,
+    last_column: usize,
+}
+
+fn parse_crate_row(line: &str) -> Result<Vec<DrawnCrate>, String> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut crates = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            c if c.is_whitespace() => i += 1,
+            '[' => {
+                let len = chars[i + 1..]
+                    .iter()
+                    .position(|c| *c == ']' || *c == '[' || c.is_whitespace())
+                    .filter(|len| *len > 0 && chars[i + 1 + len] == ']')
+                    .ok_or(format!("Crate in column {} is not closed", i + 1))?;
+
+                crates.push(DrawnCrate {
+                    name: chars[i + 1..i + 1 + len].iter().collect(),
+                    first_column: i,
+                    last_column: i + len + 1,
+                });
+                i += len + 2;
+            }
+            c => return Err(format!("Unexpected {c:?} in column {}", i + 1)),
+        }
+    }
+    return Ok(crates);
+}
+
+// Looks up the ID given to a crate label, giving the label the next free ID if it is new
+fn intern_name(ids: &mut HashMap<String, CrateId>, names: &mut Vec<String>, name: &str) -> CrateId {
+    if let Some(id) = ids.get(name) {
+        return *id;
+    }
+    let id = names.len() as CrateId;
+    ids.insert(name.to_string(), id);
+    names.push(name.to_string());
+    return id;
+}
+
 struct CrateStacks {
-    stacks: Vec<Vec<char>>,
+    stacks: Vec<Vec<CrateId>>,
+    // The label of each crate ID, as it appears in the drawing
+    names: Vec<String>,
+    history: Vec<AppliedMovement>,
+    // Lines taken up by the drawing in the input, so movements can be given their line numbers
+    drawing_lines: usize,
 }
 
 impl CrateStacks {
-    fn from_file(reader: &mut BufReader<File>) -> Result<CrateStacks, std::io::Error> {
-        let mut stacks: Vec<Vec<char>> = Vec::new();
+    // The row of stack numbers decides how many stacks there are and which column each stack is
+    // in, so wide drawings with multi-digit numbers and rows with trailing spaces removed are fine
+    fn from_file(reader: &mut impl BufRead) -> Result<CrateStacks, std::io::Error> {
+        let invalid = |msg: String| std::io::Error::new(std::io::ErrorKind::InvalidData, msg);
+        let mut rows: Vec<(usize, Vec<DrawnCrate>)> = Vec::new();
         let mut line = String::with_capacity(32);
+        let mut line_num = 0;
 
-        while reader.read_line(&mut line)? != 0 {
-            let bytes = line.as_bytes();
+        let labels = loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(invalid(String::from("Drawing has no row of stack numbers")));
+            }
+            line_num += 1;
+            let l = line.trim_end_matches(['\r', '\n']);
 
-            if (bytes[1] as char).is_ascii_digit() {
-                break;
+            if let Some(labels) = parse_label_row(l) {
+                break labels;
             }
+            rows.push((
+                line_num,
+                parse_crate_row(l).map_err(|e| invalid(format!("Line {line_num}: {e}")))?,
+            ));
+        };
 
-            while stacks.len() < (bytes.len() + 1) / 4 {
-                stacks.push(Vec::new());
+        for (i, (label, _)) in labels.iter().enumerate() {
+            if *label != i + 1 {
+                return Err(invalid(format!(
+                    "Line {line_num}: stack numbers must count up from 1, found {label} in position {}",
+                    i + 1
+                )));
             }
+        }
 
-            for (i, b) in bytes.iter().skip(1).step_by(4).enumerate() {
-                if *b != b' ' {
-                    stacks[i].push(*b as char);
+        let mut stacks: Vec<Vec<CrateId>> = vec![Vec::new(); labels.len()];
+        let mut ids = HashMap::new();
+        let mut names = Vec::new();
+        for (row_num, crates) in rows {
+            for c in crates {
+                // Compare doubled columns so a centre can fall between two characters
+                let first = c.first_column * 2;
+                let last = c.last_column * 2;
+                let (stack, centre) = labels
+                    .iter()
+                    .enumerate()
+                    .map(|(i, (_, centre))| (i, *centre))
+                    .min_by_key(|(_, centre)| centre.abs_diff(c.first_column + c.last_column))
+                    .ok_or_else(|| invalid(String::from("Drawing has no stacks")))?;
+
+                if centre < first || centre > last {
+                    return Err(invalid(format!(
+                        "Line {row_num}: crate {} in column {} is not above a stack number",
+                        c.name,
+                        c.first_column + 1
+                    )));
                 }
+                stacks[stack].push(intern_name(&mut ids, &mut names, &c.name));
             }
-            line.clear();
         }
 
         for stack in stacks.iter_mut() {
             stack.reverse();
         }
 
-        return Ok(CrateStacks { stacks });
+        return Ok(CrateStacks {
+            stacks,
+            names,
+            history: Vec::new(),
+            drawing_lines: line_num,
+        });
+    }
+
+    fn name(&self, id: CrateId) -> &str {
+        return &self.names[id as usize];
+    }
+
+    fn apply_movement(
+        &mut self,
+        crane: &dyn Crane,
+        movement: CrateMovement,
+    ) -> Result<usize, MovementError> {
+        self.check_movement(movement)?;
+        let from_stack = &self.stacks[movement.from_stack];
+        let crates = from_stack[from_stack.len() - movement.num_crates..].to_vec();
+        let lifts = crane.move_crates(self, movement)?;
+
+        self.history.push(AppliedMovement { movement, crates });
+        return Ok(lifts);
     }
 
-    fn move_crates_sequential(&mut self, movement: CrateMovement) -> Result<(), &'static str> {
-        let from_stack_len = self.stacks[movement.from_stack].len();
-        if from_stack_len < movement.num_crates {
-            return Err("Stack does not contain enough crates");
+    fn undo_movement(&mut self) -> Option<CrateMovement> {
+        let applied = self.history.pop()?;
+        let movement = applied.movement;
+        let to_stack_len = self.stacks[movement.to_stack].len();
+
+        self.stacks[movement.to_stack].truncate(to_stack_len - movement.num_crates);
+        self.stacks[movement.from_stack].extend(applied.crates);
+        return Some(movement);
+    }
+
+    fn check_movement(&self, movement: CrateMovement) -> Result<(), MovementError> {
+        let num_stacks = self.stacks.len();
+        for stack in [movement.from_stack, movement.to_stack] {
+            if stack >= num_stacks {
+                return Err(MovementError::StackOutOfRange { stack, num_stacks });
+            }
         }
 
-        let start_index = from_stack_len - 1;
-        for i in 0..movement.num_crates {
-            let c = self.stacks[movement.from_stack][start_index - i];
-            self.stacks[movement.to_stack].push(c);
+        if movement.from_stack == movement.to_stack {
+            return Err(MovementError::MoveToSelf {
+                stack: movement.from_stack,
+            });
         }
 
-        self.stacks[movement.from_stack].truncate(start_index + 1 - movement.num_crates);
+        let available = self.stacks[movement.from_stack].len();
+        if available < movement.num_crates {
+            return Err(MovementError::Underflow {
+                stack: movement.from_stack,
+                available,
+                needed: movement.num_crates,
+            });
+        }
         return Ok(());
     }
 
-    fn move_crates_grouped(&mut self, movement: CrateMovement) -> Result<(), &'static str> {
-        let from_stack_len = self.stacks[movement.from_stack].len();
-        if from_stack_len < movement.num_crates {
-            return Err("Stack does not contain enough crates");
-        }
+    // The source and destination stacks of the movement, along with the index in the source
+    // stack of the lowest crate being moved
+    fn split_movement(
+        &mut self,
+        movement: CrateMovement,
+    ) -> Result<(&mut Vec<CrateId>, &mut Vec<CrateId>, usize), MovementError> {
+        self.check_movement(movement)?;
+        let [from_stack, to_stack] = self
+            .stacks
+            .get_disjoint_mut([movement.from_stack, movement.to_stack])
+            .map_err(|_| MovementError::MoveToSelf {
+                stack: movement.from_stack,
+            })?;
+        let start_index = from_stack.len() - movement.num_crates;
 
-        let start_index = from_stack_len - movement.num_crates;
-        for i in 0..movement.num_crates {
-            let c = self.stacks[movement.from_stack][start_index + i];
-            self.stacks[movement.to_stack].push(c);
-        }
+        return Ok((from_stack, to_stack, start_index));
+    }
 
-        self.stacks[movement.from_stack].truncate(start_index);
+    // Moving the crates one at a time leaves them in reverse order, so they are appended in bulk
+    // from the top down
+    fn move_crates_sequential(&mut self, movement: CrateMovement) -> Result<(), MovementError> {
+        let (from_stack, to_stack, start_index) = self.split_movement(movement)?;
+        to_stack.extend(from_stack[start_index..].iter().rev());
+        from_stack.truncate(start_index);
         return Ok(());
     }
 
-    fn get_top_crates(&mut self) -> String {
-        let mut top_crates = String::new();
-        for stack in self.stacks.iter() {
-            let top_crate = stack[stack.len().saturating_sub(1)];
-            top_crates.push(top_crate);
+    fn move_crates_grouped(&mut self, movement: CrateMovement) -> Result<(), MovementError> {
+        let (from_stack, to_stack, start_index) = self.split_movement(movement)?;
+        to_stack.extend_from_slice(&from_stack[start_index..]);
+        from_stack.truncate(start_index);
+        return Ok(());
+    }
+
+    // Empty stacks are shown as the placeholder. Single letter crates are run together like in
+    // the puzzle answer, but longer IDs are separated by spaces so they can be told apart
+    fn get_top_crates(&self, empty_placeholder: &str) -> String {
+        let separator = if self.names.iter().any(|name| name.chars().count() > 1) {
+            " "
+        } else {
+            ""
+        };
+        let top_crates: Vec<&str> = self
+            .stacks
+            .iter()
+            .map(|stack| stack.last().map_or(empty_placeholder, |c| self.name(*c)))
+            .collect();
+
+        return top_crates.join(separator);
+    }
+
+    // Stacks of crate labels listed from bottom to top, for building arrangements in tests
+    #[cfg(test)]
+    pub(crate) fn from_ids(stacks: &[&[&str]]) -> CrateStacks {
+        let mut ids = HashMap::new();
+        let mut names = Vec::new();
+        let stacks = stacks
+            .iter()
+            .map(|stack| {
+                let stack = stack.iter();
+                stack
+                    .map(|c| intern_name(&mut ids, &mut names, c))
+                    .collect()
+            })
+            .collect();
+
+        return CrateStacks {
+            stacks,
+            names,
+            history: Vec::new(),
+            drawing_lines: 0,
+        };
+    }
+}
+
+// Draws the stacks the same way as the puzzle input, ending with the row of stack numbers
+impl fmt::Display for CrateStacks {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let height = self.stacks.iter().map(|s| s.len()).max().unwrap_or(0);
+        let id_width = self.names.iter().map(|name| name.chars().count());
+        let width = id_width.max().unwrap_or(1).max(1) + 2;
+
+        for row in (0..height).rev() {
+            let cells: Vec<String> = self
+                .stacks
+                .iter()
+                .map(|stack| match stack.get(row) {
+                    Some(c) => format!("{:^width$}", format!("[{}]", self.name(*c))),
+                    None => " ".repeat(width),
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
         }
 
-        return top_crates;
+        let labels: Vec<String> = (1..=self.stacks.len())
+            .map(|i| format!("{i:^width$}"))
+            .collect();
+        return writeln!(f, "{}", labels.join(" "));
     }
 }
 
-fn solution_part_1() -> Result<String, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
+fn run_crane(
+    path: &Path,
+    crane: &dyn Crane,
+    empty_placeholder: &str,
+) -> Result<(String, usize), Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
     let mut reader = BufReader::new(file);
     let mut line = String::with_capacity(16);
     let mut crate_stacks = CrateStacks::from_file(&mut reader)?;
+    let mut lifts = 0;
+    let mut line_num = crate_stacks.drawing_lines;
 
     while reader.read_line(&mut line)? != 0 {
+        line_num += 1;
         let l = line.trim();
 
         if !l.is_empty() {
             let crate_movement = CrateMovement::from_string(l)?;
-            crate_stacks.move_crates_sequential(crate_movement)?;
-            line.clear();
+            lifts += crane
+                .move_crates(&mut crate_stacks, crate_movement)
+                .map_err(|e| format!("Line {line_num} ({crate_movement}): {e}"))?;
         }
+        line.clear();
     }
 
-    return Ok(crate_stacks.get_top_crates());
+    return Ok((crate_stacks.get_top_crates(empty_placeholder), lifts));
 }
 
-fn solution_part_2() -> Result<String, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
-    let mut reader = BufReader::new(file);
-    let mut line = String::with_capacity(16);
-    let mut crate_stacks = CrateStacks::from_file(&mut reader)?;
+fn solution_part_1(
+    path: &Path,
+    empty_placeholder: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    return Ok(run_crane(path, &CrateMover9000, empty_placeholder)?.0);
+}
 
-    while reader.read_line(&mut line)? != 0 {
-        let l = line.trim();
+fn solution_part_2(
+    path: &Path,
+    empty_placeholder: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    return Ok(run_crane(path, &CrateMover9001, empty_placeholder)?.0);
+}
 
-        if !l.is_empty() {
-            let crate_movement = CrateMovement::from_string(l)?;
-            crate_stacks.move_crates_grouped(crate_movement)?;
-            line.clear();
+// Prints the start drawing followed by the planned movements, which together make a puzzle input
+fn print_plan(
+    path: &Path,
+    target_path: &Path,
+    crane: &dyn Crane,
+    max_arrangements: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let read_drawing = |path: &Path| -> Result<CrateStacks, Box<dyn std::error::Error>> {
+        let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+        return Ok(CrateStacks::from_file(&mut BufReader::new(file))?);
+    };
+    let start = read_drawing(path)?;
+    let target = read_drawing(target_path)?;
+
+    let plan = match planner::plan_movements(&start, &target, crane, max_arrangements)? {
+        planner::Plan::Found(plan) => plan,
+        planner::Plan::Unreachable { arrangements } => {
+            return Err(format!(
+                "The {} cannot reach the target, all {arrangements} reachable arrangements were tried",
+                crane.name()
+            ))?;
         }
-    }
+        planner::Plan::LimitReached => {
+            return Err(format!(
+                "No plan found with the {} after {max_arrangements} arrangements",
+                crane.name()
+            ))?;
+        }
+    };
 
-    return Ok(crate_stacks.get_top_crates());
+    println!("{start}");
+    for movement in plan.iter() {
+        println!("{movement}");
+    }
+    return Ok(());
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let part_1 = solution_part_1()?;
-    let part_2 = solution_part_2()?;
+    let mut args = std::env::args().skip(1);
+    let mut input = String::from("input.txt");
+    let mut crane = None;
+    let mut step_through = false;
+    let mut validate = false;
+    let mut animate_every = None;
+    let mut delay = 200;
+    let mut empty_placeholder = String::from("-");
+    let mut plan_target = None;
+    let mut plan_limit = 1_000_000;
+    let mut bench_movements = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--crane" => {
+                let name = args.next().ok_or("--crane requires a crane name")?;
+                crane = Some(crane::crane_from_name(&name)?);
+            }
+            "--step" => step_through = true,
+            "--validate" => validate = true,
+            "--empty" => {
+                empty_placeholder = args.next().ok_or("--empty requires a placeholder")?;
+            }
+            "--plan" => {
+                plan_target = Some(args.next().ok_or("--plan requires a target drawing")?);
+            }
+            "--plan-limit" => {
+                plan_limit = args
+                    .next()
+                    .ok_or("--plan-limit requires a number of arrangements")?
+                    .parse()?;
+            }
+            "--animate" => {
+                let every = args.next().ok_or("--animate requires a number of moves")?;
+                animate_every = Some(every.parse::<usize>()?);
+            }
+            "--delay" => {
+                delay = args
+                    .next()
+                    .ok_or("--delay requires a number of milliseconds")?
+                    .parse()?;
+            }
+            "--bench" => {
+                let movements = args
+                    .next()
+                    .ok_or("--bench requires a number of movements")?;
+                bench_movements = Some(movements.parse::<usize>()?);
+            }
+            _ => input = arg,
+        }
+    }
+
+    if let Some(movements) = bench_movements {
+        return bench::compare_movers(movements);
+    }
+
+    if let Some(target) = plan_target {
+        let crane = crane.unwrap_or_else(|| Box::new(CrateMover9000));
+        return print_plan(
+            Path::new(&input),
+            Path::new(&target),
+            crane.as_ref(),
+            plan_limit,
+        );
+    }
+
+    if let Some(every) = animate_every {
+        let crane = crane.unwrap_or_else(|| Box::new(CrateMover9000));
+        return animate::animate(
+            Path::new(&input),
+            crane.as_ref(),
+            every,
+            delay,
+            &empty_placeholder,
+        );
+    }
+
+    if validate {
+        let crane = crane.unwrap_or_else(|| Box::new(CrateMover9000));
+        return validate::validate(Path::new(&input), crane.as_ref());
+    }
+
+    if step_through {
+        let crane = crane.unwrap_or_else(|| Box::new(CrateMover9000));
+        return step::step_through(Path::new(&input), crane.as_ref());
+    }
+
+    if let Some(crane) = crane {
+        let (top_crates, lifts) = run_crane(Path::new(&input), crane.as_ref(), &empty_placeholder)?;
+        println!(
+            "{} - Top crates: {top_crates} ({lifts} lifts)",
+            crane.name()
+        );
+        return Ok(());
+    }
+
+    let part_1 = solution_part_1(Path::new(&input), &empty_placeholder)?;
+    let part_2 = solution_part_2(Path::new(&input), &empty_placeholder)?;
 
     println!("Part 1 - Top crates: {part_1}");
     println!("Part 2 - Top crates: {part_2}");
     return Ok(());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Each drawing numbers its crates in the order it reads them, so stacks are compared by label
+    fn stack_names(crate_stacks: &CrateStacks) -> Vec<Vec<&str>> {
+        return crate_stacks
+            .stacks
+            .iter()
+            .map(|stack| stack.iter().map(|c| crate_stacks.name(*c)).collect())
+            .collect();
+    }
+
+    fn assert_round_trip(stacks: &[&[&str]]) {
+        let original = CrateStacks::from_ids(stacks);
+        let drawing = original.to_string();
+        let parsed = CrateStacks::from_file(&mut drawing.as_bytes())
+            .unwrap_or_else(|e| panic!("{e}, parsing:\n{drawing}"));
+
+        assert_eq!(
+            stack_names(&parsed),
+            stack_names(&original),
+            "drawing:\n{drawing}"
+        );
+    }
+
+    #[test]
+    fn round_trip_puzzle_example() {
+        assert_round_trip(&[&["Z", "N"], &["M", "C", "D"], &["P"]]);
+    }
+
+    #[test]
+    fn round_trip_empty_stacks() {
+        assert_round_trip(&[&[], &["A", "B"], &[]]);
+        assert_round_trip(&[&[], &[]]);
+    }
+
+    #[test]
+    fn round_trip_more_than_nine_stacks() {
+        let names = ["A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L"];
+        let stacks: Vec<&[&str]> = (0..names.len()).map(|i| &names[..i % 4]).collect();
+        assert_round_trip(&stacks);
+    }
+
+    #[test]
+    fn top_crates_separate_multi_character_ids() {
+        let single = CrateStacks::from_ids(&[&[], &["G"], &["H", "C"]]);
+        assert_eq!(single.get_top_crates("-"), "-GC");
+
+        let multi = CrateStacks::from_ids(&[&[], &["G"], &["H", "C", "AB", "DEF"]]);
+        assert_eq!(multi.get_top_crates("-"), "- G DEF");
+    }
+
+    #[test]
+    fn round_trip_multi_character_ids() {
+        assert_round_trip(&[&["X"], &[], &["C", "AB"], &["LONG"]]);
+        assert_round_trip(&[&["AB", "DEF"], &["G"], &[], &["H", "C", "AB", "DEF"]]);
+    }
+}
@@ -0,0 +1,310 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::crane::Crane;
+use crate::{CrateId, CrateMovement, CrateStacks};
+
+// The crates in each stack from bottom to top, without any movement history
+type Arrangement = Vec<Vec<CrateId>>;
+
+// Crates above the part of a stack that already matches the bottom of the target stack. Each of
+// them has to be lifted off at least once, whichever crane is used
+fn misplaced_crates(stack: &[CrateId], target: &[CrateId]) -> usize {
+    let matching = stack.iter().zip(target).take_while(|(a, b)| a == b).count();
+    return stack.len() - matching;
+}
+
+// Every stack holding misplaced crates needs at least one move taking crates off it, and a
+// single move only takes crates off one stack, so this never overestimates the moves left
+fn estimate_moves(stacks: &[Vec<CrateId>], target: &[Vec<CrateId>]) -> usize {
+    return stacks
+        .iter()
+        .zip(target)
+        .filter(|(stack, target)| misplaced_crates(stack, target) > 0)
+        .count();
+}
+
+// Each drawing numbers its crates in the order it reads them, so the target arrangement is
+// renumbered to use the IDs the start drawing gave the same labels
+fn renumber_target(start: &CrateStacks, target: &CrateStacks) -> Result<Arrangement, String> {
+    let ids: HashMap<&str, CrateId> = start
+        .names
+        .iter()
+        .enumerate()
+        .map(|(id, name)| (name.as_str(), id as CrateId))
+        .collect();
+
+    return target
+        .stacks
+        .iter()
+        .map(|stack| {
+            stack
+                .iter()
+                .map(|c| {
+                    let name = target.name(*c);
+                    return ids.get(name).copied().ok_or(format!(
+                        "Crate {name} is in the target drawing but not the start drawing"
+                    ));
+                })
+                .collect()
+        })
+        .collect();
+}
+
+fn check_same_crates(start: &[Vec<CrateId>], target: &[Vec<CrateId>]) -> Result<(), String> {
+    if start.len() != target.len() {
+        return Err(format!(
+            "Start drawing has {} stacks but the target has {}",
+            start.len(),
+            target.len()
+        ));
+    }
+
+    let mut start_crates: Vec<&CrateId> = start.iter().flatten().collect();
+    let mut target_crates: Vec<&CrateId> = target.iter().flatten().collect();
+    start_crates.sort_unstable();
+    target_crates.sort_unstable();
+    if start_crates != target_crates {
+        return Err(String::from(
+            "Start and target drawings must contain the same crates",
+        ));
+    }
+    return Ok(());
+}
+
+// Every movement that the crane could carry out from this arrangement, along with the result
+fn next_arrangements(
+    stacks: &[Vec<CrateId>],
+    crane: &dyn Crane,
+) -> Result<Vec<(CrateMovement, Arrangement)>, String> {
+    let mut arrangements = Vec::new();
+
+    for from_stack in 0..stacks.len() {
+        for to_stack in (0..stacks.len()).filter(|s| *s != from_stack) {
+            for num_crates in 1..=stacks[from_stack].len() {
+                let movement = CrateMovement {
+                    num_crates,
+                    from_stack,
+                    to_stack,
+                };
+                let mut crate_stacks = CrateStacks {
+                    stacks: stacks.to_vec(),
+                    names: Vec::new(),
+                    history: Vec::new(),
+                    drawing_lines: 0,
+                };
+
+                crane
+                    .move_crates(&mut crate_stacks, movement)
+                    .map_err(|e| format!("{movement}: {e}"))?;
+                arrangements.push((movement, crate_stacks.stacks));
+            }
+        }
+    }
+    return Ok(arrangements);
+}
+
+pub enum Plan {
+    Found(Vec<CrateMovement>),
+    // Every arrangement the crane can reach was tried without finding the target
+    Unreachable { arrangements: usize },
+    // The search gave up after seeing the maximum number of arrangements
+    LimitReached,
+}
+
+// A* search for the fewest movements that turn the start arrangement into the target one
+pub fn plan_movements(
+    start: &CrateStacks,
+    target: &CrateStacks,
+    crane: &dyn Crane,
+    max_arrangements: usize,
+) -> Result<Plan, String> {
+    let target = renumber_target(start, target)?;
+    check_same_crates(&start.stacks, &target)?;
+
+    // Arrangements are numbered in the order they are found, remembering the arrangement and
+    // movement that first reached each one so the plan can be traced back from the target
+    let mut arrangements: Vec<Arrangement> = vec![start.stacks.clone()];
+    let mut reached_from: Vec<Option<(usize, CrateMovement)>> = vec![None];
+    let mut moves_to: Vec<usize> = vec![0];
+    let mut ids: HashMap<Arrangement, usize> = HashMap::from([(start.stacks.clone(), 0)]);
+    let mut queue = BinaryHeap::from([Reverse((estimate_moves(&start.stacks, &target), 0, 0))]);
+
+    while let Some(Reverse((_, moves, id))) = queue.pop() {
+        if moves > moves_to[id] {
+            continue;
+        }
+
+        if arrangements[id] == target {
+            let mut plan = Vec::new();
+            let mut current = id;
+            while let Some((previous, movement)) = reached_from[current] {
+                plan.push(movement);
+                current = previous;
+            }
+            plan.reverse();
+            return Ok(Plan::Found(plan));
+        }
+
+        for (movement, stacks) in next_arrangements(&arrangements[id], crane)? {
+            let next_moves = moves + 1;
+            let estimate = next_moves + estimate_moves(&stacks, &target);
+
+            match ids.get(&stacks) {
+                Some(&next_id) if moves_to[next_id] <= next_moves => {}
+                Some(&next_id) => {
+                    moves_to[next_id] = next_moves;
+                    reached_from[next_id] = Some((id, movement));
+                    queue.push(Reverse((estimate, next_moves, next_id)));
+                }
+                None => {
+                    if arrangements.len() >= max_arrangements {
+                        return Ok(Plan::LimitReached);
+                    }
+                    let next_id = arrangements.len();
+                    ids.insert(stacks.clone(), next_id);
+                    arrangements.push(stacks);
+                    moves_to.push(next_moves);
+                    reached_from.push(Some((id, movement)));
+                    queue.push(Reverse((estimate, next_moves, next_id)));
+                }
+            }
+        }
+    }
+
+    return Ok(Plan::Unreachable {
+        arrangements: arrangements.len(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashSet, VecDeque};
+
+    use super::*;
+    use crate::crane::{CapacityCrane, CrateMover9000, CrateMover9001};
+
+    // Breadth-first search without the estimate, for checking the plans are as short as possible
+    fn fewest_moves(start: &CrateStacks, target: &[Vec<CrateId>], crane: &dyn Crane) -> usize {
+        let mut seen = HashSet::from([start.stacks.clone()]);
+        let mut queue = VecDeque::from([(start.stacks.clone(), 0)]);
+
+        while let Some((stacks, moves)) = queue.pop_front() {
+            if stacks == target {
+                return moves;
+            }
+            for (_, next) in next_arrangements(&stacks, crane).expect("Moves should be valid") {
+                if seen.insert(next.clone()) {
+                    queue.push_back((next, moves + 1));
+                }
+            }
+        }
+        panic!("Target is unreachable");
+    }
+
+    fn check_plan(start: &[&[&str]], target: &[&[&str]], crane: &dyn Crane, expected_moves: usize) {
+        let start = CrateStacks::from_ids(start);
+        let target = CrateStacks::from_ids(target);
+        let target_ids = renumber_target(&start, &target).expect("Target has the same crates");
+        let plan = match plan_movements(&start, &target, crane, 100_000) {
+            Ok(Plan::Found(plan)) => plan,
+            _ => panic!("{} should find a plan", crane.name()),
+        };
+
+        let mut replayed = CrateStacks::from_ids(&[]);
+        replayed.stacks = start.stacks.clone();
+        for movement in plan.iter() {
+            crane
+                .move_crates(&mut replayed, *movement)
+                .unwrap_or_else(|e| panic!("{movement}: {e}"));
+        }
+
+        assert_eq!(replayed.stacks, target_ids, "{}", crane.name());
+        assert_eq!(plan.len(), expected_moves, "{}", crane.name());
+        assert_eq!(plan.len(), fewest_moves(&start, &target_ids, crane));
+    }
+
+    #[test]
+    fn crate_mover_9000_plans() {
+        let crane = CrateMover9000;
+        check_plan(
+            &[&["A", "B"], &[], &[]],
+            &[&[], &["A", "B"], &[]],
+            &crane,
+            2,
+        );
+        check_plan(
+            &[&["A", "B"], &[], &[]],
+            &[&[], &["B", "A"], &[]],
+            &crane,
+            1,
+        );
+        check_plan(
+            &[&["Z", "N"], &["M", "C", "D"], &["P"]],
+            &[&["C"], &["M"], &["P", "D", "N", "Z"]],
+            &crane,
+            3,
+        );
+    }
+
+    #[test]
+    fn crate_mover_9001_plans() {
+        let crane = CrateMover9001;
+        check_plan(
+            &[&["A", "B"], &[], &[]],
+            &[&[], &["A", "B"], &[]],
+            &crane,
+            1,
+        );
+        check_plan(
+            &[&["A", "B"], &[], &[]],
+            &[&[], &["B", "A"], &[]],
+            &crane,
+            2,
+        );
+        check_plan(&[&["A", "B", "C"], &[]], &[&["A"], &["B", "C"]], &crane, 1);
+    }
+
+    #[test]
+    fn capacity_crane_plans() {
+        let crane = CapacityCrane::new(2).expect("Capacity is valid");
+        check_plan(
+            &[&["A", "B"], &[], &[]],
+            &[&[], &["A", "B"], &[]],
+            &crane,
+            1,
+        );
+        check_plan(
+            &[&["A", "B", "C"], &[], &[]],
+            &[&[], &["A", "B", "C"], &[]],
+            &crane,
+            3,
+        );
+    }
+
+    #[test]
+    fn targets_must_have_the_same_crates() {
+        let start = CrateStacks::from_ids(&[&["A", "B"], &[]]);
+        let missing = CrateStacks::from_ids(&[&["A"], &["C"]]);
+        let extra = CrateStacks::from_ids(&[&["A"], &["B", "B"]]);
+        assert!(plan_movements(&start, &missing, &CrateMover9001, 100).is_err());
+        assert!(plan_movements(&start, &extra, &CrateMover9001, 100).is_err());
+    }
+
+    #[test]
+    fn unreachable_and_limited_searches() {
+        let start = CrateStacks::from_ids(&[&["A", "B"]]);
+        let target = CrateStacks::from_ids(&[&["B", "A"]]);
+        assert!(matches!(
+            plan_movements(&start, &target, &CrateMover9001, 100),
+            Ok(Plan::Unreachable { arrangements: 1 })
+        ));
+
+        let start = CrateStacks::from_ids(&[&["A", "B", "C"], &[], &[]]);
+        let target = CrateStacks::from_ids(&[&[], &[], &["A", "B", "C"]]);
+        assert!(matches!(
+            plan_movements(&start, &target, &CrateMover9000, 2),
+            Ok(Plan::LimitReached)
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use crate::crane::Crane;
+use crate::{CrateMovement, CrateStacks};
+
+fn print_stacks(crate_stacks: &CrateStacks, step: usize, num_steps: usize) {
+    println!("Step {step} of {num_steps}");
+    print!("{crate_stacks}");
+}
+
+fn step_forward(
+    crate_stacks: &mut CrateStacks,
+    crane: &dyn Crane,
+    movements: &[CrateMovement],
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let step = crate_stacks.history.len();
+    let movement = match movements.get(step) {
+        Some(m) => *m,
+        None => return Ok(false),
+    };
+
+    crate_stacks
+        .apply_movement(crane, movement)
+        .map_err(|e| format!("Step {} ({movement}): {e}", step + 1))?;
+    return Ok(true);
+}
+
+// Commands are read from stdin: "n [count]" steps forward, "b [count]" steps back, "j <step>"
+// jumps to a step, "p" prints the stacks and "q" quits
+pub fn step_through(path: &Path, crane: &dyn Crane) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let mut reader = BufReader::new(file);
+    let mut crate_stacks = CrateStacks::from_file(&mut reader)?;
+    let mut movements = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let l = line.trim();
+
+        if !l.is_empty() {
+            movements.push(CrateMovement::from_string(l)?);
+        }
+    }
+
+    print_stacks(&crate_stacks, 0, movements.len());
+    let mut commands = std::io::stdin().lock().lines();
+
+    loop {
+        print!("> ");
+        std::io::stdout().flush()?;
+
+        let command = match commands.next() {
+            Some(command) => command?,
+            None => break,
+        };
+        let mut tokens = command.split_whitespace();
+        let name = tokens.next().unwrap_or("p");
+        let count = match tokens.next().map(|t| t.parse::<usize>()) {
+            Some(Ok(count)) => Some(count),
+            Some(Err(e)) => {
+                println!("Invalid number: {e}");
+                continue;
+            }
+            None => None,
+        };
+
+        let target = match (name, count) {
+            ("n" | "next", count) => crate_stacks.history.len() + count.unwrap_or(1),
+            ("b" | "back", count) => crate_stacks
+                .history
+                .len()
+                .saturating_sub(count.unwrap_or(1)),
+            ("j" | "jump", Some(step)) => step,
+            ("p" | "print", _) => crate_stacks.history.len(),
+            ("q" | "quit", _) => break,
+            _ => {
+                println!("Commands: n [count], b [count], j <step>, p, q");
+                continue;
+            }
+        };
+        let target = target.min(movements.len());
+
+        while crate_stacks.history.len() > target {
+            if let Some(movement) = crate_stacks.undo_movement() {
+                println!("Undo {movement}");
+            }
+        }
+        while crate_stacks.history.len() < target {
+            if let Err(e) = step_forward(&mut crate_stacks, crane, &movements) {
+                println!("{e}");
+                break;
+            }
+            println!("{}", movements[crate_stacks.history.len() - 1]);
+        }
+
+        print_stacks(&crate_stacks, crate_stacks.history.len(), movements.len());
+    }
+    return Ok(());
+}
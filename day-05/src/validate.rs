@@ -0,0 +1,54 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::crane::Crane;
+use crate::{CrateMovement, CrateStacks};
+
+// Carries out every movement it can and skips the rest, printing each skipped one along with the
+// stack heights at that point. Lines that are not movements at all are reported the same way
+pub fn validate(path: &Path, crane: &dyn Crane) -> Result<(), Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?;
+    let mut reader = BufReader::new(file);
+    let mut line = String::with_capacity(16);
+    let mut crate_stacks = CrateStacks::from_file(&mut reader)?;
+    let mut line_num = crate_stacks.drawing_lines;
+    let mut num_movements = 0;
+    let mut num_invalid = 0;
+
+    while reader.read_line(&mut line)? != 0 {
+        line_num += 1;
+        let l = line.trim();
+
+        if !l.is_empty() {
+            num_movements += 1;
+            let error = match CrateMovement::from_string(l) {
+                Ok(movement) => match crane.move_crates(&mut crate_stacks, movement) {
+                    Ok(_) => None,
+                    Err(e) => Some(e.to_string()),
+                },
+                Err(e) => Some(format!("Unreadable movement, {e}")),
+            };
+
+            if let Some(error) = error {
+                let heights: Vec<String> = crate_stacks
+                    .stacks
+                    .iter()
+                    .map(|s| s.len().to_string())
+                    .collect();
+                println!("Line {line_num}: {l}");
+                println!("    {error}");
+                println!("    Stack heights: {}", heights.join(" "));
+                num_invalid += 1;
+            }
+        }
+        line.clear();
+    }
+
+    if num_invalid == 0 {
+        println!("All {num_movements} movements are valid");
+    } else {
+        println!("{num_invalid} of {num_movements} movements are invalid");
+    }
+    return Ok(());
+}
@@ -0,0 +1,97 @@
+use std::time::{Duration, Instant};
+
+use crate::find_marker;
+
+const MARKER_LENGTHS: [usize; 4] = [4, 14, 64, 256];
+
+// The previous detector, which compares every pair of bytes in the window at each position
+fn find_marker_pairwise(
+    bytes: impl Iterator<Item = Result<u8, std::io::Error>>,
+    marker_length: usize,
+) -> Result<usize, std::io::Error> {
+    let mut byte_buffer = Vec::with_capacity(marker_length);
+    let mut buffer_index = 0;
+
+    'outer: for (i, byte) in bytes.enumerate() {
+        if byte_buffer.len() < marker_length {
+            byte_buffer.push(byte?);
+        } else {
+            byte_buffer[buffer_index] = byte?;
+            buffer_index = (buffer_index + 1) % marker_length;
+        }
+
+        if byte_buffer.len() == marker_length {
+            for (j, byte_1) in byte_buffer.iter().enumerate() {
+                for (k, byte_2) in byte_buffer.iter().enumerate() {
+                    if j != k && byte_1 == byte_2 {
+                        continue 'outer;
+                    }
+                }
+            }
+            return Ok(i + 1);
+        }
+    }
+    return Result::Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "No marker found",
+    ))?;
+}
+
+// Pseudo-random bytes from an alphabet one byte smaller than the marker, followed by a run of
+// distinct bytes, so both detectors have to read the whole stream before finding a marker
+fn late_marker_stream(len: usize, marker_length: usize) -> Vec<u8> {
+    let alphabet = (marker_length - 1).max(1) as u64;
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+
+    let mut stream: Vec<u8> = (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % alphabet) as u8
+        })
+        .collect();
+    stream.extend((0..marker_length).map(|b| b as u8));
+    return stream;
+}
+
+fn stream_bytes(stream: &[u8]) -> impl Iterator<Item = Result<u8, std::io::Error>> + '_ {
+    return stream.iter().map(|b| Ok(*b));
+}
+
+fn time_detector(
+    detector: impl Fn(&[u8]) -> Result<usize, std::io::Error>,
+    stream: &[u8],
+) -> (Option<usize>, Duration) {
+    let start = Instant::now();
+    let position = detector(stream).ok();
+    return (position, start.elapsed());
+}
+
+pub fn compare_detectors(megabytes: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let len = megabytes * 1024 * 1024;
+    println!("Searching {megabytes} MB streams with a marker at the end");
+    println!("Length  Pairwise (ms)  Counting (ms)  Speed-up");
+
+    for marker_length in MARKER_LENGTHS {
+        let stream = late_marker_stream(len, marker_length);
+        let (pairwise, pairwise_time) = time_detector(
+            |s| find_marker_pairwise(stream_bytes(s), marker_length),
+            &stream,
+        );
+        let (counting, counting_time) = time_detector(|s| find_marker(s, marker_length), &stream);
+
+        if pairwise != counting {
+            return Err(format!(
+                "Detectors disagree for length {marker_length}: {pairwise:?} and {counting:?}"
+            ))?;
+        }
+        println!(
+            "{marker_length:>6}  {:>13.1}  {:>13.1}  {:>7.1}x",
+            pairwise_time.as_secs_f64() * 1000.0,
+            counting_time.as_secs_f64() * 1000.0,
+            pairwise_time.as_secs_f64() / counting_time.as_secs_f64()
+        );
+    }
+    return Ok(());
+}
@@ -1,56 +1,89 @@
+mod bench;
+mod markers;
+
+use markers::Markers;
 use std::fs::File;
-use std::io::{BufReader, Bytes, Read};
+use std::io::Read;
+use std::path::Path;
 
-fn find_marker(
-    bytes: Bytes<BufReader<File>>,
-    marker_length: usize,
-) -> Result<usize, std::io::Error> {
-    let mut byte_buffer = Vec::with_capacity(marker_length);
-    let mut buffer_index = 0;
-
-    'outer: for (i, byte) in bytes.enumerate() {
-        if byte_buffer.len() < marker_length {
-            byte_buffer.push(byte?);
-        } else {
-            byte_buffer[buffer_index] = byte?;
-            buffer_index = (buffer_index + 1) % marker_length;
-        }
+fn find_marker(reader: impl Read, marker_length: usize) -> Result<usize, std::io::Error> {
+    return match Markers::new(reader, marker_length)?.next() {
+        Some(position) => position,
+        None => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "No marker found",
+        )),
+    };
+}
 
-        // This block needs to be separate to prevent an edge case where pushing a byte to the
-        // byte_buffer vector changes its length to equal marker_length. If this block of code
-        // was in the else statement it would then end up skipping the first potential marker
-        if byte_buffer.len() == marker_length {
-            for (j, byte_1) in byte_buffer.iter().enumerate() {
-                for (k, byte_2) in byte_buffer.iter().enumerate() {
-                    if j != k && byte_1 == byte_2 {
-                        continue 'outer;
-                    }
-                }
-            }
-            return Ok(i + 1);
-        }
-    }
-    return Result::Err(std::io::Error::new(
-        std::io::ErrorKind::NotFound,
-        "No marker found",
-    ))?;
+fn open_input(path: &Path) -> Result<File, Box<dyn std::error::Error>> {
+    return Ok(File::open(path).map_err(|e| format!("Error opening {path:?}: {e:?}"))?);
 }
 
-fn solution_part_1() -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
-    let bytes = BufReader::new(file).bytes();
-    return Ok(find_marker(bytes, 4)?);
+fn solution_part_1(path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    return Ok(find_marker(open_input(path)?, 4)?);
 }
 
-fn solution_part_2() -> Result<usize, Box<dyn std::error::Error>> {
-    let file = File::open("input.txt").map_err(|e| format!("Error opening input.txt: {e:?}"))?;
-    let bytes = BufReader::new(file).bytes();
-    return Ok(find_marker(bytes, 14)?);
+fn solution_part_2(path: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    return Ok(find_marker(open_input(path)?, 14)?);
+}
+
+// Lists every marker in the input rather than only the first
+fn print_markers(
+    path: &Path,
+    marker_length: usize,
+    overlapping: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let file = open_input(path)?;
+    let markers = if overlapping {
+        Markers::new(file, marker_length)?
+    } else {
+        Markers::non_overlapping(file, marker_length)?
+    };
+    let mut num_markers = 0;
+
+    for position in markers {
+        println!("{}", position?);
+        num_markers += 1;
+    }
+    println!("Found {num_markers} markers of length {marker_length}");
+    return Ok(());
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let part_1 = solution_part_1()?;
-    let part_2 = solution_part_2()?;
+    let mut args = std::env::args().skip(1);
+    let mut input = String::from("input.txt");
+    let mut bench_megabytes = None;
+    let mut marker_length = None;
+    let mut overlapping = true;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--bench" => {
+                let megabytes = args
+                    .next()
+                    .ok_or("--bench requires a number of megabytes")?;
+                bench_megabytes = Some(megabytes.parse::<usize>()?);
+            }
+            "--markers" => {
+                let length = args.next().ok_or("--markers requires a marker length")?;
+                marker_length = Some(length.parse::<usize>()?);
+            }
+            "--non-overlapping" => overlapping = false,
+            _ => input = arg,
+        }
+    }
+
+    if let Some(megabytes) = bench_megabytes {
+        return bench::compare_detectors(megabytes);
+    }
+
+    if let Some(marker_length) = marker_length {
+        return print_markers(Path::new(&input), marker_length, overlapping);
+    }
+
+    let part_1 = solution_part_1(Path::new(&input))?;
+    let part_2 = solution_part_2(Path::new(&input))?;
 
     println!("Part 1 - First marker found at character {part_1}");
     println!("Part 2 - First message found at character {part_2}");
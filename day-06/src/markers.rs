@@ -0,0 +1,277 @@
+use std::io::Read;
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+// Every byte value can only appear once in a marker, so no marker can be longer than this
+pub const MAX_MARKER_LENGTH: usize = 256;
+
+// The most recent bytes of the stream, kept as a ring buffer along with how often each byte
+// value appears in it and how many of these counts are non-zero. Each byte is handled in
+// constant time however long the marker is
+struct Window {
+    bytes: Vec<u8>,
+    // Position in `bytes` of the oldest byte, which the next byte replaces
+    oldest: usize,
+    // Bytes pushed since the window was last cleared, which can be more than fit in it
+    pushed: usize,
+    counts: [usize; 256],
+    distinct: usize,
+}
+
+impl Window {
+    fn new(marker_length: usize) -> Self {
+        return Window {
+            bytes: vec![0; marker_length],
+            oldest: 0,
+            pushed: 0,
+            counts: [0; 256],
+            distinct: 0,
+        };
+    }
+
+    fn clear(&mut self) {
+        self.oldest = 0;
+        self.pushed = 0;
+        self.counts = [0; 256];
+        self.distinct = 0;
+    }
+
+    // Slides the window over the bytes, returning how many of them it took for the window to hold
+    // a marker. The loop works on local copies so they can stay in registers
+    fn find_marker(&mut self, bytes: &[u8]) -> Option<usize> {
+        let marker_length = self.bytes.len();
+        let mut oldest = self.oldest;
+        let mut pushed = self.pushed;
+        let mut distinct = self.distinct;
+        let mut found = None;
+
+        for (i, byte) in bytes.iter().enumerate() {
+            if pushed >= marker_length {
+                let old_byte = self.bytes[oldest] as usize;
+                self.counts[old_byte] -= 1;
+                if self.counts[old_byte] == 0 {
+                    distinct -= 1;
+                }
+            }
+
+            self.bytes[oldest] = *byte;
+            oldest = if oldest + 1 == marker_length {
+                0
+            } else {
+                oldest + 1
+            };
+            pushed += 1;
+            if self.counts[*byte as usize] == 0 {
+                distinct += 1;
+            }
+            self.counts[*byte as usize] += 1;
+
+            if distinct == marker_length {
+                found = Some(i + 1);
+                break;
+            }
+        }
+
+        self.oldest = oldest;
+        self.pushed = pushed;
+        self.distinct = distinct;
+        return found;
+    }
+}
+
+// Yields the position just after every run of `marker_length` distinct bytes, counting from 1
+// like the puzzle does. Overlapping markers can share bytes, while non-overlapping ones start
+// looking for the next marker after the end of the last one
+pub struct Markers<R: Read> {
+    reader: R,
+    // Bytes are read in chunks and `buffer[next..]` are the ones not yet looked at
+    buffer: Vec<u8>,
+    next: usize,
+    window: Window,
+    overlapping: bool,
+    position: usize,
+}
+
+impl<R: Read> Markers<R> {
+    pub fn new(reader: R, marker_length: usize) -> Result<Self, std::io::Error> {
+        if marker_length == 0 || marker_length > MAX_MARKER_LENGTH {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("Marker length must be between 1 and {MAX_MARKER_LENGTH}"),
+            ));
+        }
+
+        return Ok(Markers {
+            reader,
+            buffer: Vec::with_capacity(READ_BUFFER_SIZE),
+            next: 0,
+            window: Window::new(marker_length),
+            overlapping: true,
+            position: 0,
+        });
+    }
+
+    pub fn non_overlapping(reader: R, marker_length: usize) -> Result<Self, std::io::Error> {
+        let mut markers = Markers::new(reader, marker_length)?;
+        markers.overlapping = false;
+        return Ok(markers);
+    }
+
+    // Returns false once the reader has run out of bytes
+    fn fill_buffer(&mut self) -> Result<bool, std::io::Error> {
+        self.buffer.resize(READ_BUFFER_SIZE, 0);
+        self.next = 0;
+
+        loop {
+            match self.reader.read(&mut self.buffer) {
+                Ok(len) => {
+                    self.buffer.truncate(len);
+                    return Ok(len > 0);
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.buffer.clear();
+                    return Err(e);
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for Markers<R> {
+    type Item = Result<usize, std::io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let unread = &self.buffer[self.next..];
+
+            if let Some(used) = self.window.find_marker(unread) {
+                self.next += used;
+                self.position += used;
+                if !self.overlapping {
+                    self.window.clear();
+                }
+                return Some(Ok(self.position));
+            }
+            self.position += unread.len();
+
+            match self.fill_buffer() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_distinct(window: &[u8]) -> bool {
+        let mut seen = [false; 256];
+        for byte in window {
+            if seen[*byte as usize] {
+                return false;
+            }
+            seen[*byte as usize] = true;
+        }
+        return true;
+    }
+
+    // Checks every window in turn, skipping past each marker when markers cannot overlap
+    fn brute_force_markers(stream: &[u8], marker_length: usize, overlapping: bool) -> Vec<usize> {
+        let mut markers = Vec::new();
+        let mut end = marker_length;
+
+        while end <= stream.len() {
+            if all_distinct(&stream[end - marker_length..end]) {
+                markers.push(end);
+                if !overlapping {
+                    end += marker_length;
+                    continue;
+                }
+            }
+            end += 1;
+        }
+        return markers;
+    }
+
+    fn collect_markers(stream: &[u8], marker_length: usize, overlapping: bool) -> Vec<usize> {
+        let markers = if overlapping {
+            Markers::new(stream, marker_length)
+        } else {
+            Markers::non_overlapping(stream, marker_length)
+        };
+        return markers
+            .expect("Marker length is valid")
+            .collect::<Result<_, _>>()
+            .expect("Reading from a slice cannot fail");
+    }
+
+    // Pseudo-random bytes from a small alphabet, so longer markers are rare, with runs of every
+    // byte value dropped in. One of the runs straddles the end of the first chunk read
+    fn test_stream() -> Vec<u8> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut stream: Vec<u8> = (0..200_000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state % 16) as u8
+            })
+            .collect();
+
+        for start in [1_000, READ_BUFFER_SIZE - 100, 150_000] {
+            for (i, byte) in (0..=u8::MAX).enumerate() {
+                stream[start + i] = byte;
+            }
+        }
+        return stream;
+    }
+
+    #[test]
+    fn markers_match_brute_force() {
+        let stream = test_stream();
+        for marker_length in [1, 2, 4, 14, 64, MAX_MARKER_LENGTH] {
+            for overlapping in [true, false] {
+                let markers = collect_markers(&stream, marker_length, overlapping);
+                assert!(!markers.is_empty(), "length {marker_length}");
+                assert_eq!(
+                    markers,
+                    brute_force_markers(&stream, marker_length, overlapping),
+                    "length {marker_length}, overlapping {overlapping}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn markers_across_chunks() {
+        let stream = test_stream();
+        let across = READ_BUFFER_SIZE - 100 + MAX_MARKER_LENGTH;
+        for overlapping in [true, false] {
+            let markers = collect_markers(&stream, MAX_MARKER_LENGTH, overlapping);
+            assert!(markers.contains(&across), "overlapping {overlapping}");
+        }
+    }
+
+    #[test]
+    fn single_byte_markers() {
+        assert_eq!(collect_markers(b"aab", 1, true), vec![1, 2, 3]);
+        assert_eq!(collect_markers(b"aab", 1, false), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn streams_without_markers() {
+        assert!(collect_markers(b"", 1, true).is_empty());
+        assert!(collect_markers(b"abcabcabc", 4, true).is_empty());
+        assert!(collect_markers(&[7; 100_000], 2, false).is_empty());
+    }
+
+    #[test]
+    fn marker_lengths_out_of_range() {
+        assert!(Markers::new(&b"abc"[..], 0).is_err());
+        assert!(Markers::new(&b"abc"[..], MAX_MARKER_LENGTH + 1).is_err());
+    }
+}